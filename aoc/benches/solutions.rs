@@ -0,0 +1,15 @@
+use std::fs;
+
+use aoc::{bench, registry};
+
+const ITERATIONS: usize = 100;
+
+fn main() {
+    for day in registry::DAYS {
+        let content = fs::read_to_string(day.input).expect("Failed to read input");
+        let stats = bench::bench_day(day, &content, ITERATIONS).expect("Failed to parse input");
+        println!("{}", bench::format_stats(&format!("day{}/parse", day.number), &stats.parse));
+        println!("{}", bench::format_stats(&format!("day{}/part1", day.number), &stats.part1));
+        println!("{}", bench::format_stats(&format!("day{}/part2", day.number), &stats.part2));
+    }
+}
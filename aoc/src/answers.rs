@@ -0,0 +1,115 @@
+use std::collections::HashMap;
+
+/// Recorded answers keyed by `(day, part)`, read from a small TOML subset:
+///
+/// ```toml
+/// [day1]
+/// part1 = 1666427
+/// part2 = "text answers are quoted"
+/// ```
+#[derive(Debug, Default, PartialEq)]
+pub struct Answers {
+    answers: HashMap<(u8, u8), String>,
+}
+
+fn parse_key(key: &str, prefix: &str) -> Option<u8> {
+    key.strip_prefix(prefix)?.parse().ok()
+}
+
+/// Drops a trailing `#` comment, leaving any `#` inside a quoted answer.
+fn strip_comment(line: &str) -> &str {
+    let mut quoted = false;
+    for (index, c) in line.char_indices() {
+        match c {
+            '"' => quoted = !quoted,
+            '#' if !quoted => return &line[..index],
+            _ => {}
+        }
+    }
+    line
+}
+
+fn parse_value(value: &str) -> Option<String> {
+    if let Some(quoted) = value.strip_prefix('"') {
+        return quoted.strip_suffix('"').map(|text| text.to_string());
+    }
+    value.parse::<i128>().ok().map(|number| number.to_string())
+}
+
+impl Answers {
+    pub fn parse(input: &str) -> Result<Self, String> {
+        let mut answers = HashMap::new();
+        let mut day = None;
+
+        for (index, line) in input.lines().enumerate() {
+            let line_number = index + 1;
+            let line = strip_comment(line).trim();
+            if line.is_empty() {
+                continue;
+            }
+
+            if let Some(section) = line.strip_prefix('[').and_then(|s| s.strip_suffix(']')) {
+                let number = parse_key(section.trim(), "day")
+                    .ok_or_else(|| format!("line {}: invalid section [{}]", line_number, section))?;
+                day = Some(number);
+                continue;
+            }
+
+            let day = day.ok_or_else(|| format!("line {}: answer outside of a [dayN] section", line_number))?;
+            let (key, value) = line
+                .split_once('=')
+                .ok_or_else(|| format!("line {}: expected `partN = answer`", line_number))?;
+            let part = parse_key(key.trim(), "part")
+                .ok_or_else(|| format!("line {}: invalid key {}", line_number, key.trim()))?;
+            let value = parse_value(value.trim())
+                .ok_or_else(|| format!("line {}: invalid answer {}", line_number, value.trim()))?;
+            answers.insert((day, part), value);
+        }
+
+        Ok(Self { answers })
+    }
+
+    pub fn get(&self, day: u8, part: u8) -> Option<&str> {
+        self.answers.get(&(day, part)).map(|answer| answer.as_str())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_answers() {
+        let input = r#"
+        # Recorded answers
+        [day1]
+        part1 = 11
+        part2 = 31 # similarity
+
+        [day7]
+        part1 = "ABC"
+        "#;
+        let answers = Answers::parse(input).expect("Failed to parse answers");
+        assert_eq!(answers.get(1, 1), Some("11"));
+        assert_eq!(answers.get(1, 2), Some("31"));
+        assert_eq!(answers.get(7, 1), Some("ABC"));
+        assert_eq!(answers.get(7, 2), None);
+    }
+
+    #[test]
+    fn test_parse_hash_in_answer() {
+        let input = "[day1]\npart1 = \"a#b\" # quoted\npart2 = \"#\"\n";
+        let answers = Answers::parse(input).expect("Failed to parse answers");
+        assert_eq!(answers.get(1, 1), Some("a#b"));
+        assert_eq!(answers.get(1, 2), Some("#"));
+    }
+
+    #[test]
+    fn test_parse_invalid_answers() {
+        assert!(Answers::parse("part1 = 1").is_err());
+        assert!(Answers::parse("[dayx]\npart1 = 1").is_err());
+        assert!(Answers::parse("[day1]\npart1 = x").is_err());
+        assert!(Answers::parse("[day1]\nfirst = 1").is_err());
+        assert!(Answers::parse("[day1]\npart1").is_err());
+    }
+}
@@ -0,0 +1,92 @@
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use crate::registry::Day;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+}
+
+impl Stats {
+    pub fn from_samples(samples: &[Duration]) -> Stats {
+        assert!(!samples.is_empty(), "No samples to summarise");
+        let mut sorted = samples.to_vec();
+        sorted.sort();
+        let middle = sorted.len() / 2;
+        let median = if sorted.len().is_multiple_of(2) {
+            (sorted[middle - 1] + sorted[middle]) / 2
+        } else {
+            sorted[middle]
+        };
+        let total: Duration = sorted.iter().sum();
+        Stats {
+            min: sorted[0],
+            median,
+            mean: total / sorted.len() as u32,
+        }
+    }
+}
+
+/// Runs `f` once to warm up, then `iterations` times, timing each run.
+pub fn measure<T, F: FnMut() -> T>(iterations: usize, mut f: F) -> Stats {
+    black_box(f());
+    let samples: Vec<Duration> = (0..iterations.max(1))
+        .map(|_| {
+            let start = Instant::now();
+            black_box(f());
+            start.elapsed()
+        })
+        .collect();
+    Stats::from_samples(&samples)
+}
+
+#[derive(Debug)]
+pub struct DayStats {
+    pub parse: Stats,
+    pub part1: Stats,
+    pub part2: Stats,
+}
+
+pub fn bench_day(day: &Day, content: &str, iterations: usize) -> Result<DayStats, String> {
+    let parsed = day.solver.parse(content).map_err(|e| e.to_string())?;
+    Ok(DayStats {
+        parse: measure(iterations, || day.solver.parse(content)),
+        part1: measure(iterations, || parsed.part1()),
+        part2: measure(iterations, || parsed.part2()),
+    })
+}
+
+pub fn format_stats(label: &str, stats: &Stats) -> String {
+    format!(
+        "{:<16} min {:>10.2?}  median {:>10.2?}  mean {:>10.2?}",
+        label, stats.min, stats.median, stats.mean
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn millis(values: &[u64]) -> Vec<Duration> {
+        values.iter().map(|&ms| Duration::from_millis(ms)).collect()
+    }
+
+    #[test]
+    fn test_stats_odd_samples() {
+        let stats = Stats::from_samples(&millis(&[5, 1, 3]));
+        assert_eq!(stats.min, Duration::from_millis(1));
+        assert_eq!(stats.median, Duration::from_millis(3));
+        assert_eq!(stats.mean, Duration::from_millis(3));
+    }
+
+    #[test]
+    fn test_stats_even_samples() {
+        let stats = Stats::from_samples(&millis(&[4, 1, 2, 9]));
+        assert_eq!(stats.min, Duration::from_millis(1));
+        assert_eq!(stats.median, Duration::from_millis(3));
+        assert_eq!(stats.mean, Duration::from_millis(4));
+    }
+}
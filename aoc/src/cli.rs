@@ -0,0 +1,453 @@
+use aoc::output::Format;
+use day1::metric::Metric;
+use day1::Mismatch;
+
+#[derive(Debug, PartialEq)]
+pub enum Command {
+    Run(RunArgs),
+    Verify(VerifyArgs),
+    Bench(BenchArgs),
+    Render(RenderArgs),
+    Compare(CompareArgs),
+    Reports(ReportsArgs),
+}
+
+#[derive(Debug, PartialEq)]
+pub struct RunArgs {
+    pub day: u8,
+    pub part: Option<u8>,
+    pub format: Format,
+    /// Input files to solve, `-` reads stdin. Empty means the day's own input.
+    pub inputs: Vec<String>,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct VerifyArgs {
+    pub answers: Option<String>,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct BenchArgs {
+    pub day: Option<u8>,
+    pub iterations: usize,
+}
+
+/// Draws the day 4 grid with the matches of one part highlighted.
+#[derive(Debug, PartialEq)]
+pub struct RenderArgs {
+    pub part: u8,
+    pub colour: bool,
+    pub svg: bool,
+    /// Grid to render, `-` reads stdin. `None` means the day's own input.
+    pub input: Option<String>,
+}
+
+/// Compares the day 1 columns with a chosen metric.
+#[derive(Debug, PartialEq)]
+pub struct CompareArgs {
+    pub metric: Metric,
+    /// Zero-based pair of columns to compare. `None` prints the full matrix.
+    pub columns: Option<(usize, usize)>,
+    pub mismatch: Mismatch,
+    /// Lists to compare, `-` reads stdin. `None` means the day's own input.
+    pub input: Option<String>,
+}
+
+/// Classifies the day 2 reports.
+#[derive(Debug, PartialEq)]
+pub struct ReportsArgs {
+    /// Levels the dampener may remove from each report.
+    pub tolerance: usize,
+    /// Print why each report is safe or unsafe before the summary.
+    pub explain: bool,
+    /// Threads classifying the streamed reports.
+    pub threads: usize,
+    /// Reports to classify, `-` reads stdin. `None` means the day's own input.
+    pub input: Option<String>,
+}
+
+const DEFAULT_TOLERANCE: usize = 1;
+
+const DEFAULT_ITERATIONS: usize = 100;
+
+pub fn usage(program: &str) -> String {
+    [
+        format!("Usage: {} run --day <day> [--part <part>] [--format text|json] [<input>|-]...", program),
+        format!("       {} verify [--answers <file>]", program),
+        format!("       {} bench [--day <day>] [--iterations <n>]", program),
+        format!("       {} render [--part <part>] [--color] [--svg] [<input>|-]", program),
+        format!(
+            "       {} compare [--metric <metric>] [--columns <a>,<b>|--matrix] [--mismatch error|skip|pad] [<input>|-]",
+            program
+        ),
+        format!("       {} reports [--tolerance <k>] [--explain] [--threads <n>] [<input>|-]", program),
+    ]
+    .join("\n")
+}
+
+fn parse_value<T: std::str::FromStr>(flag: &str, value: Option<&String>) -> Result<T, String> {
+    let value = value.ok_or_else(|| format!("Missing value for {}", flag))?;
+    value
+        .parse()
+        .map_err(|_| format!("Invalid value for {}: {}", flag, value))
+}
+
+fn parse_run(args: &[String]) -> Result<RunArgs, String> {
+    let mut day = None;
+    let mut part = None;
+    let mut format = Format::default();
+    let mut inputs = Vec::new();
+    let mut args = args.iter();
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--day" => day = Some(parse_value(arg, args.next())?),
+            "--part" => part = Some(parse_value(arg, args.next())?),
+            "--format" => format = parse_value(arg, args.next())?,
+            flag if flag.starts_with("--") => return Err(format!("Unknown flag {}", flag)),
+            path => inputs.push(path.to_string()),
+        }
+    }
+
+    Ok(RunArgs {
+        day: day.ok_or("Missing --day")?,
+        part,
+        format,
+        inputs,
+    })
+}
+
+fn parse_verify(args: &[String]) -> Result<VerifyArgs, String> {
+    let mut answers = None;
+    let mut args = args.iter();
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--answers" => {
+                let path = args.next().ok_or_else(|| format!("Missing value for {}", arg))?;
+                answers = Some(path.to_string());
+            }
+            other => return Err(format!("Unexpected argument {}", other)),
+        }
+    }
+
+    Ok(VerifyArgs { answers })
+}
+
+fn parse_bench(args: &[String]) -> Result<BenchArgs, String> {
+    let mut day = None;
+    let mut iterations = DEFAULT_ITERATIONS;
+    let mut args = args.iter();
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--day" => day = Some(parse_value(arg, args.next())?),
+            "--iterations" => iterations = parse_value(arg, args.next())?,
+            other => return Err(format!("Unexpected argument {}", other)),
+        }
+    }
+
+    Ok(BenchArgs { day, iterations })
+}
+
+fn parse_render(args: &[String]) -> Result<RenderArgs, String> {
+    let mut part = 1;
+    let mut colour = false;
+    let mut svg = false;
+    let mut input = None;
+    let mut args = args.iter();
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--part" => part = parse_value(arg, args.next())?,
+            "--color" | "--colour" => colour = true,
+            "--svg" => svg = true,
+            flag if flag.starts_with("--") => return Err(format!("Unknown flag {}", flag)),
+            path if input.is_none() => input = Some(path.to_string()),
+            other => return Err(format!("Unexpected argument {}", other)),
+        }
+    }
+    if !matches!(part, 1 | 2) {
+        return Err(format!("Invalid part {}", part));
+    }
+
+    Ok(RenderArgs { part, colour, svg, input })
+}
+
+/// Parses a one-based `a,b` column pair into zero-based indices.
+fn parse_columns(value: Option<&String>) -> Result<(usize, usize), String> {
+    let invalid = || format!("Invalid value for --columns: {}", value.map_or("", |v| v.as_str()));
+    let value = value.ok_or("Missing value for --columns")?;
+    let (a, b) = value.split_once(',').ok_or_else(invalid)?;
+    let index = |text: &str| match text.trim().parse::<usize>() {
+        Ok(column) if column > 0 => Ok(column - 1),
+        _ => Err(invalid()),
+    };
+    Ok((index(a)?, index(b)?))
+}
+
+fn parse_compare(args: &[String]) -> Result<CompareArgs, String> {
+    let mut metric = Metric::default();
+    let mut columns = Some((0, 1));
+    let mut mismatch = Mismatch::default();
+    let mut input = None;
+    let mut args = args.iter();
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--metric" => metric = parse_value(arg, args.next())?,
+            "--columns" => columns = Some(parse_columns(args.next())?),
+            "--matrix" => columns = None,
+            "--mismatch" => mismatch = parse_value(arg, args.next())?,
+            flag if flag.starts_with("--") => return Err(format!("Unknown flag {}", flag)),
+            path if input.is_none() => input = Some(path.to_string()),
+            other => return Err(format!("Unexpected argument {}", other)),
+        }
+    }
+
+    Ok(CompareArgs { metric, columns, mismatch, input })
+}
+
+fn parse_reports(args: &[String]) -> Result<ReportsArgs, String> {
+    let mut tolerance = DEFAULT_TOLERANCE;
+    let mut explain = false;
+    let mut threads = 1;
+    let mut input = None;
+    let mut args = args.iter();
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--tolerance" => tolerance = parse_value(arg, args.next())?,
+            "--explain" => explain = true,
+            "--threads" => threads = parse_value(arg, args.next())?,
+            flag if flag.starts_with("--") => return Err(format!("Unknown flag {}", flag)),
+            path if input.is_none() => input = Some(path.to_string()),
+            other => return Err(format!("Unexpected argument {}", other)),
+        }
+    }
+
+    if threads == 0 {
+        return Err("Invalid value for --threads: 0".to_string());
+    }
+
+    Ok(ReportsArgs { tolerance, explain, threads, input })
+}
+
+pub fn parse_args(args: &[String]) -> Result<Command, String> {
+    match args.first().map(|arg| arg.as_str()) {
+        Some("run") => Ok(Command::Run(parse_run(&args[1..])?)),
+        Some("verify") => Ok(Command::Verify(parse_verify(&args[1..])?)),
+        Some("bench") => Ok(Command::Bench(parse_bench(&args[1..])?)),
+        Some("render") => Ok(Command::Render(parse_render(&args[1..])?)),
+        Some("compare") => Ok(Command::Compare(parse_compare(&args[1..])?)),
+        Some("reports") => Ok(Command::Reports(parse_reports(&args[1..])?)),
+        Some(command) => Err(format!("Unknown command {}", command)),
+        None => Err("Missing command".to_string()),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn args(line: &str) -> Vec<String> {
+        line.split_whitespace().map(|arg| arg.to_string()).collect()
+    }
+
+    #[test]
+    fn test_parse_run() {
+        assert_eq!(
+            parse_args(&args("run --day 5 --part 2 input")),
+            Ok(Command::Run(RunArgs {
+                day: 5,
+                part: Some(2),
+                format: Format::Text,
+                inputs: vec!["input".to_string()],
+            })),
+        );
+    }
+
+    #[test]
+    fn test_parse_run_without_part() {
+        assert_eq!(
+            parse_args(&args("run input --day 1")),
+            Ok(Command::Run(RunArgs {
+                day: 1,
+                part: None,
+                format: Format::Text,
+                inputs: vec!["input".to_string()],
+            })),
+        );
+    }
+
+    #[test]
+    fn test_parse_run_inputs() {
+        assert_eq!(
+            parse_args(&args("run --day 3")),
+            Ok(Command::Run(RunArgs {
+                day: 3,
+                part: None,
+                format: Format::Text,
+                inputs: vec![],
+            })),
+        );
+        assert_eq!(
+            parse_args(&args("run --day 3 a.txt - b.txt")),
+            Ok(Command::Run(RunArgs {
+                day: 3,
+                part: None,
+                format: Format::Text,
+                inputs: vec!["a.txt".to_string(), "-".to_string(), "b.txt".to_string()],
+            })),
+        );
+    }
+
+    #[test]
+    fn test_parse_run_json() {
+        assert_eq!(
+            parse_args(&args("run --day 2 --format json input")),
+            Ok(Command::Run(RunArgs {
+                day: 2,
+                part: None,
+                format: Format::Json,
+                inputs: vec!["input".to_string()],
+            })),
+        );
+        assert!(parse_args(&args("run --day 2 --format yaml input")).is_err());
+    }
+
+    #[test]
+    fn test_parse_verify() {
+        assert_eq!(
+            parse_args(&args("verify")),
+            Ok(Command::Verify(VerifyArgs { answers: None })),
+        );
+        assert_eq!(
+            parse_args(&args("verify --answers answers.toml")),
+            Ok(Command::Verify(VerifyArgs {
+                answers: Some("answers.toml".to_string()),
+            })),
+        );
+        assert!(parse_args(&args("verify --answers")).is_err());
+        assert!(parse_args(&args("verify input")).is_err());
+    }
+
+    #[test]
+    fn test_parse_bench() {
+        assert_eq!(
+            parse_args(&args("bench")),
+            Ok(Command::Bench(BenchArgs {
+                day: None,
+                iterations: DEFAULT_ITERATIONS,
+            })),
+        );
+        assert_eq!(
+            parse_args(&args("bench --day 4 --iterations 10")),
+            Ok(Command::Bench(BenchArgs {
+                day: Some(4),
+                iterations: 10,
+            })),
+        );
+        assert!(parse_args(&args("bench --iterations many")).is_err());
+    }
+
+    #[test]
+    fn test_parse_render() {
+        assert_eq!(
+            parse_args(&args("render")),
+            Ok(Command::Render(RenderArgs {
+                part: 1,
+                colour: false,
+                svg: false,
+                input: None,
+            })),
+        );
+        assert_eq!(
+            parse_args(&args("render --part 2 --color --svg grid.txt")),
+            Ok(Command::Render(RenderArgs {
+                part: 2,
+                colour: true,
+                svg: true,
+                input: Some("grid.txt".to_string()),
+            })),
+        );
+        assert!(parse_args(&args("render --part 3")).is_err());
+        assert!(parse_args(&args("render a.txt b.txt")).is_err());
+    }
+
+    #[test]
+    fn test_parse_compare() {
+        assert_eq!(
+            parse_args(&args("compare")),
+            Ok(Command::Compare(CompareArgs {
+                metric: Metric::Distance,
+                columns: Some((0, 1)),
+                mismatch: Mismatch::Error,
+                input: None,
+            })),
+        );
+        assert_eq!(
+            parse_args(&args("compare --metric emd --columns 3,1 --mismatch pad lists.txt")),
+            Ok(Command::Compare(CompareArgs {
+                metric: Metric::EarthMovers,
+                columns: Some((2, 0)),
+                mismatch: Mismatch::Pad,
+                input: Some("lists.txt".to_string()),
+            })),
+        );
+        assert_eq!(
+            parse_args(&args("compare --matrix --metric jaccard -")),
+            Ok(Command::Compare(CompareArgs {
+                metric: Metric::Jaccard,
+                columns: None,
+                mismatch: Mismatch::Error,
+                input: Some("-".to_string()),
+            })),
+        );
+        assert!(parse_args(&args("compare --metric cosine")).is_err());
+        assert!(parse_args(&args("compare --columns 0,1")).is_err());
+        assert!(parse_args(&args("compare --columns 1")).is_err());
+        assert!(parse_args(&args("compare --mismatch ignore")).is_err());
+    }
+
+    #[test]
+    fn test_parse_reports() {
+        assert_eq!(
+            parse_args(&args("reports")),
+            Ok(Command::Reports(ReportsArgs {
+                tolerance: DEFAULT_TOLERANCE,
+                explain: false,
+                threads: 1,
+                input: None,
+            })),
+        );
+        assert_eq!(
+            parse_args(&args("reports --tolerance 3 --explain reports.txt")),
+            Ok(Command::Reports(ReportsArgs {
+                tolerance: 3,
+                explain: true,
+                threads: 1,
+                input: Some("reports.txt".to_string()),
+            })),
+        );
+        assert_eq!(
+            parse_args(&args("reports --threads 4 -")),
+            Ok(Command::Reports(ReportsArgs {
+                tolerance: DEFAULT_TOLERANCE,
+                explain: false,
+                threads: 4,
+                input: Some("-".to_string()),
+            })),
+        );
+        assert!(parse_args(&args("reports --tolerance -1")).is_err());
+        assert!(parse_args(&args("reports --threads 0")).is_err());
+    }
+
+    #[test]
+    fn test_parse_invalid_args() {
+        assert!(parse_args(&args("run --part 1 input")).is_err());
+        assert!(parse_args(&args("run --day x input")).is_err());
+        assert!(parse_args(&args("run --day 1 --verbose input")).is_err());
+        assert!(parse_args(&args("solve --day 1 input")).is_err());
+    }
+}
@@ -0,0 +1,4 @@
+pub mod answers;
+pub mod bench;
+pub mod output;
+pub mod registry;
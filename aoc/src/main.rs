@@ -0,0 +1,237 @@
+use std::io::{self, BufRead, BufReader, Read};
+use std::time::Instant;
+use std::{env, fs, process};
+
+mod cli;
+
+use aoc::answers::Answers;
+use aoc::output::{Format, Record};
+use aoc::{bench, output, registry};
+use common::Solution;
+use day2::stream::Tally;
+use day4::render::Highlight;
+use cli::{BenchArgs, Command, CompareArgs, RenderArgs, ReportsArgs, RunArgs, VerifyArgs};
+
+const ANSWERS: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/../answers.toml");
+
+const STDIN: &str = "-";
+
+fn read_input(path: &str) -> Result<String, String> {
+    if path == STDIN {
+        let mut content = String::new();
+        io::stdin()
+            .read_to_string(&mut content)
+            .map_err(|e| format!("Error reading stdin: {}", e))?;
+        return Ok(content);
+    }
+    fs::read_to_string(path).map_err(|e| format!("Error reading {}: {}", path, e))
+}
+
+fn open_input(path: &str) -> Result<Box<dyn BufRead>, String> {
+    if path == STDIN {
+        return Ok(Box::new(io::stdin().lock()));
+    }
+    let file = fs::File::open(path).map_err(|e| format!("Error reading {}: {}", path, e))?;
+    Ok(Box::new(BufReader::new(file)))
+}
+
+fn run(args: &RunArgs) -> Result<(), String> {
+    let day = registry::find(args.day).ok_or_else(|| format!("Day {} is not registered", args.day))?;
+    let inputs = match args.inputs.as_slice() {
+        [] => vec![day.input],
+        inputs => inputs.iter().map(|input| input.as_str()).collect(),
+    };
+    let parts = match args.part {
+        Some(part) => vec![part],
+        None => vec![1, 2],
+    };
+
+    for input in &inputs {
+        let name = if *input == STDIN { "<stdin>" } else { input };
+        if inputs.len() > 1 && args.format == Format::Text {
+            println!("==> {} <==", name);
+        }
+        let content = read_input(input)?;
+        let parsed = day
+            .solver
+            .parse(&content)
+            .map_err(|e| format!("Error parsing {}: {}", name, e))?;
+        for &part in &parts {
+            let start = Instant::now();
+            let answer = parsed.part(part).ok_or_else(|| format!("Invalid part {}", part))?;
+            let record = Record {
+                input: name,
+                day: day.number,
+                part,
+                answer: &answer,
+                elapsed: start.elapsed(),
+            };
+            println!("{}", output::format_answer(args.format, &record));
+        }
+    }
+    Ok(())
+}
+
+fn verify(args: &VerifyArgs) -> Result<(), String> {
+    let path = args.answers.as_deref().unwrap_or(ANSWERS);
+    let content = fs::read_to_string(path).map_err(|e| format!("Error reading {}: {}", path, e))?;
+    let answers = Answers::parse(&content).map_err(|e| format!("Error parsing {}: {}", path, e))?;
+
+    let mut failed = 0;
+    for day in registry::DAYS {
+        let content = match fs::read_to_string(day.input) {
+            Ok(content) => content,
+            Err(e) => {
+                println!("Day {}: FAIL error reading {}: {}", day.number, day.input, e);
+                failed += 2;
+                continue;
+            }
+        };
+        let parsed = match day.solver.parse(&content) {
+            Ok(parsed) => parsed,
+            Err(e) => {
+                println!("Day {}: FAIL error parsing input: {}", day.number, e);
+                failed += 2;
+                continue;
+            }
+        };
+        for (part, answer) in [(1, parsed.part1()), (2, parsed.part2())] {
+            let answer = answer.to_string();
+            match answers.get(day.number, part) {
+                Some(expected) if expected == answer => {
+                    println!("Day {} part {}: pass", day.number, part);
+                }
+                Some(expected) => {
+                    failed += 1;
+                    println!(
+                        "Day {} part {}: FAIL expected {}, got {}",
+                        day.number, part, expected, answer
+                    );
+                }
+                None => println!("Day {} part {}: missing (got {})", day.number, part, answer),
+            }
+        }
+    }
+
+    if failed > 0 {
+        return Err(format!("Verification failed for {} answer(s)", failed));
+    }
+    Ok(())
+}
+
+fn bench(args: &BenchArgs) -> Result<(), String> {
+    let days: Vec<&registry::Day> = match args.day {
+        Some(number) => {
+            vec![registry::find(number).ok_or_else(|| format!("Day {} is not registered", number))?]
+        }
+        None => registry::DAYS.iter().collect(),
+    };
+
+    for day in days {
+        let content = fs::read_to_string(day.input)
+            .map_err(|e| format!("Error reading {}: {}", day.input, e))?;
+        let stats = bench::bench_day(day, &content, args.iterations)
+            .map_err(|e| format!("Error parsing {}: {}", day.input, e))?;
+        println!("{}", bench::format_stats(&format!("Day {} parse", day.number), &stats.parse));
+        println!("{}", bench::format_stats(&format!("Day {} part 1", day.number), &stats.part1));
+        println!("{}", bench::format_stats(&format!("Day {} part 2", day.number), &stats.part2));
+    }
+    Ok(())
+}
+
+fn render(args: &RenderArgs) -> Result<(), String> {
+    let path = args.input.as_deref().unwrap_or(day4::INPUT);
+    let content = read_input(path)?;
+    let grid = day4::Grid::parse(&content).map_err(|e| format!("Error parsing {}: {}", path, e))?;
+
+    let xmas = day4::xmas();
+    let highlights: Vec<Highlight> = match args.part {
+        1 => day4::search::find_word(&grid, "XMAS").iter().map(Highlight::from).collect(),
+        _ => day4::pattern::find_pattern(&grid, &xmas).iter().map(Highlight::from).collect(),
+    };
+    if args.svg {
+        print!("{}", day4::render::svg(&grid, &highlights));
+    } else {
+        print!("{}", day4::render::text(&grid, &highlights, args.colour));
+    }
+    Ok(())
+}
+
+fn compare(args: &CompareArgs) -> Result<(), String> {
+    let path = args.input.as_deref().unwrap_or(day1::INPUT);
+    let columns: Vec<Vec<i64>> = day1::read::read_columns(open_input(path)?, 2..=usize::MAX, args.mismatch)
+        .map_err(|e| format!("Error parsing {}: {}", path, e))?;
+
+    let error = |e| format!("Error comparing {}: {}", path, e);
+    match args.columns {
+        Some((a, b)) => {
+            let answer = args.metric.compute_pair(&columns, a, b, args.mismatch).map_err(error)?;
+            println!("{} of columns {} and {}: {}", args.metric.name(), a + 1, b + 1, answer);
+        }
+        None => {
+            for row in args.metric.matrix(&columns, args.mismatch).map_err(error)? {
+                let row: Vec<String> = row.iter().map(|answer| answer.to_string()).collect();
+                println!("{}", row.join("\t"));
+            }
+        }
+    }
+    Ok(())
+}
+
+fn reports(args: &ReportsArgs) -> Result<(), String> {
+    let path = args.input.as_deref().unwrap_or(day2::INPUT);
+    let policy = day2::SafetyPolicy::default();
+    let tally = if args.explain {
+        let content = read_input(path)?;
+        let rows = day2::Day2
+            .parse(&content)
+            .map_err(|e| format!("Error parsing {}: {}", path, e))?;
+        let mut tally = Tally::default();
+        for (index, row) in rows.iter().enumerate() {
+            let levels: Vec<String> = row.iter().map(|level| level.to_string()).collect();
+            let verdict = day2::explain::explain(row, &policy, args.tolerance);
+            println!("Report {} [{}]: {}", index + 1, levels.join(" "), verdict);
+            tally.add(day2::stream::classify(row, &policy, args.tolerance));
+        }
+        tally
+    } else {
+        day2::stream::classify_stream(open_input(path)?, &policy, args.tolerance, args.threads)
+            .map_err(|e| format!("Error parsing {}: {}", path, e))?
+    };
+    println!(
+        "Safe reports with tolerance {}: {} of {} ({} safe, {} dampened, {} unsafe)",
+        args.tolerance,
+        tally.safe + tally.dampened,
+        tally.total(),
+        tally.safe,
+        tally.dampened,
+        tally.failed,
+    );
+    Ok(())
+}
+
+fn main() {
+    let args: Vec<String> = env::args().collect();
+
+    let command = match cli::parse_args(&args[1..]) {
+        Ok(command) => command,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            eprintln!("{}", cli::usage(&args[0]));
+            process::exit(1);
+        }
+    };
+
+    let result = match command {
+        Command::Run(run_args) => run(&run_args),
+        Command::Verify(verify_args) => verify(&verify_args),
+        Command::Bench(bench_args) => bench(&bench_args),
+        Command::Render(render_args) => render(&render_args),
+        Command::Compare(compare_args) => compare(&compare_args),
+        Command::Reports(reports_args) => reports(&reports_args),
+    };
+    if let Err(e) = result {
+        eprintln!("Error: {}", e);
+        process::exit(1);
+    }
+}
@@ -0,0 +1,117 @@
+use std::str::FromStr;
+use std::time::Duration;
+
+use common::Answer;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum Format {
+    #[default]
+    Text,
+    Json,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(Format::Text),
+            "json" => Ok(Format::Json),
+            other => Err(format!("Unknown format {}", other)),
+        }
+    }
+}
+
+fn escape_json(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len() + 2);
+    escaped.push('"');
+    for c in text.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if (c as u32) < 0x20 => escaped.push_str(&format!("\\u{:04x}", c as u32)),
+            c => escaped.push(c),
+        }
+    }
+    escaped.push('"');
+    escaped
+}
+
+fn answer_json(answer: &Answer) -> String {
+    match answer {
+        Answer::Number(value) => value.to_string(),
+        Answer::Text(value) => escape_json(value),
+    }
+}
+
+pub struct Record<'a> {
+    pub input: &'a str,
+    pub day: u8,
+    pub part: u8,
+    pub answer: &'a Answer,
+    pub elapsed: Duration,
+}
+
+/// Formats one answer as a single line, either human readable or as a JSON record.
+pub fn format_answer(format: Format, record: &Record) -> String {
+    match format {
+        Format::Text => format!("Day {} part {}: {}", record.day, record.part, record.answer),
+        Format::Json => format!(
+            r#"{{"input":{},"day":{},"part":{},"answer":{},"elapsed_ns":{}}}"#,
+            escape_json(record.input),
+            record.day,
+            record.part,
+            answer_json(record.answer),
+            record.elapsed.as_nanos()
+        ),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn record<'a>(day: u8, part: u8, answer: &'a Answer, elapsed_ns: u64) -> Record<'a> {
+        Record {
+            input: "input",
+            day,
+            part,
+            answer,
+            elapsed: Duration::from_nanos(elapsed_ns),
+        }
+    }
+
+    #[test]
+    fn test_format_text() {
+        let answer = Answer::from(11);
+        assert_eq!(
+            format_answer(Format::Text, &record(1, 1, &answer, 5)),
+            "Day 1 part 1: 11",
+        );
+    }
+
+    #[test]
+    fn test_format_json() {
+        let answer = Answer::from(11);
+        assert_eq!(
+            format_answer(Format::Json, &record(1, 2, &answer, 1500)),
+            r#"{"input":"input","day":1,"part":2,"answer":11,"elapsed_ns":1500}"#,
+        );
+
+        let answer = Answer::from("a \"b\"\n");
+        assert_eq!(
+            format_answer(Format::Json, &record(7, 1, &answer, 0)),
+            r#"{"input":"input","day":7,"part":1,"answer":"a \"b\"\n","elapsed_ns":0}"#,
+        );
+    }
+
+    #[test]
+    fn test_parse_format() {
+        assert_eq!("json".parse(), Ok(Format::Json));
+        assert_eq!("text".parse(), Ok(Format::Text));
+        assert!("yaml".parse::<Format>().is_err());
+    }
+}
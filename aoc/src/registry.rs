@@ -0,0 +1,19 @@
+use common::Solver;
+
+pub struct Day {
+    pub number: u8,
+    pub solver: &'static dyn Solver,
+    pub input: &'static str,
+}
+
+pub const DAYS: &[Day] = &[
+    Day { number: 1, solver: &day1::Day1, input: day1::INPUT },
+    Day { number: 2, solver: &day2::Day2, input: day2::INPUT },
+    Day { number: 3, solver: &day3::Day3, input: day3::INPUT },
+    Day { number: 4, solver: &day4::Day4, input: day4::INPUT },
+    Day { number: 5, solver: &day5::Day5, input: day5::INPUT },
+];
+
+pub fn find(number: u8) -> Option<&'static Day> {
+    DAYS.iter().find(|day| day.number == number)
+}
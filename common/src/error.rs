@@ -0,0 +1,98 @@
+use std::{error, fmt, io};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ParseErrorKind {
+    InvalidNumber,
+    MissingValue(&'static str),
+    Expected(&'static str),
+    RowLength { expected: usize, found: usize },
+}
+
+impl fmt::Display for ParseErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseErrorKind::InvalidNumber => write!(f, "invalid number"),
+            ParseErrorKind::MissingValue(what) => write!(f, "missing {}", what),
+            ParseErrorKind::Expected(what) => write!(f, "expected {}", what),
+            ParseErrorKind::RowLength { expected, found } => {
+                write!(f, "expected {} columns, found {}", expected, found)
+            }
+        }
+    }
+}
+
+/// A parse failure pointing at the offending text. Lines and columns are 1-based.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    pub text: String,
+    pub kind: ParseErrorKind,
+}
+
+impl ParseError {
+    pub fn new(line: usize, column: usize, text: &str, kind: ParseErrorKind) -> Self {
+        Self {
+            line,
+            column,
+            text: text.to_string(),
+            kind,
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: {} in `{}`",
+            self.line, self.column, self.kind, self.text
+        )
+    }
+}
+
+impl error::Error for ParseError {}
+
+/// Failure while streaming input from a reader.
+#[derive(Debug)]
+pub enum ReadError {
+    Io(io::Error),
+    Parse(ParseError),
+}
+
+impl fmt::Display for ReadError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ReadError::Io(e) => write!(f, "read failed: {}", e),
+            ReadError::Parse(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl error::Error for ReadError {}
+
+impl From<io::Error> for ReadError {
+    fn from(e: io::Error) -> Self {
+        ReadError::Io(e)
+    }
+}
+
+impl From<ParseError> for ReadError {
+    fn from(e: ParseError) -> Self {
+        ReadError::Parse(e)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_display() {
+        let error = ParseError::new(3, 5, "12x", ParseErrorKind::InvalidNumber);
+        assert_eq!(error.to_string(), "line 3, column 5: invalid number in `12x`");
+
+        let error = ParseError::new(1, 4, "abc", ParseErrorKind::RowLength { expected: 4, found: 3 });
+        assert_eq!(error.to_string(), "line 1, column 4: expected 4 columns, found 3 in `abc`");
+    }
+}
@@ -0,0 +1,10 @@
+mod answer;
+mod error;
+pub mod parse;
+#[cfg(feature = "testing")]
+pub mod random;
+mod solution;
+
+pub use answer::Answer;
+pub use error::{ParseError, ParseErrorKind, ReadError};
+pub use solution::{Parsed, Solution, Solver};
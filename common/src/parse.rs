@@ -0,0 +1,57 @@
+use std::str::FromStr;
+
+use crate::{ParseError, ParseErrorKind};
+
+fn column_of(line: &str, field: &str) -> usize {
+    let offset = field.as_ptr() as usize - line.as_ptr() as usize;
+    line[..offset].chars().count() + 1
+}
+
+/// Splits `line` on whitespace, yielding each field with its 1-based column.
+pub fn columns(line: &str) -> impl Iterator<Item = (usize, &str)> {
+    line.split_whitespace()
+        .map(move |field| (column_of(line, field), field))
+}
+
+/// Splits `line` on `separator`, yielding each trimmed field with its 1-based column.
+pub fn split_columns(line: &str, separator: char) -> impl Iterator<Item = (usize, &str)> {
+    line.split(separator).map(move |field| {
+        let field = field.trim();
+        (column_of(line, field), field)
+    })
+}
+
+pub fn parse_number<T: FromStr>(line: usize, column: usize, text: &str) -> Result<T, ParseError> {
+    text.parse()
+        .map_err(|_| ParseError::new(line, column, text, ParseErrorKind::InvalidNumber))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_columns() {
+        assert_eq!(
+            columns("  3   41").collect::<Vec<_>>(),
+            vec![(3, "3"), (7, "41")],
+        );
+    }
+
+    #[test]
+    fn test_split_columns() {
+        assert_eq!(
+            split_columns("47| 53", '|').collect::<Vec<_>>(),
+            vec![(1, "47"), (5, "53")],
+        );
+    }
+
+    #[test]
+    fn test_parse_number() {
+        assert_eq!(parse_number::<i32>(1, 1, "42"), Ok(42));
+        assert_eq!(
+            parse_number::<i32>(2, 7, "4x"),
+            Err(ParseError::new(2, 7, "4x", ParseErrorKind::InvalidNumber)),
+        );
+    }
+}
@@ -0,0 +1,92 @@
+use crate::{Answer, ParseError};
+
+/// A puzzle solution: the input is parsed once and both parts run against it.
+pub trait Solution {
+    type Input;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, ParseError>;
+    fn part1(&self, input: &Self::Input) -> Answer;
+    fn part2(&self, input: &Self::Input) -> Answer;
+}
+
+/// Object safe view of a `Solution`, used by the runner to dispatch days.
+pub trait Solver {
+    fn parse<'a>(&'a self, input: &str) -> Result<Box<dyn Parsed + 'a>, ParseError>;
+}
+
+/// A parsed input bound to the solution that produced it.
+pub trait Parsed {
+    fn part1(&self) -> Answer;
+    fn part2(&self) -> Answer;
+
+    fn part(&self, part: u8) -> Option<Answer> {
+        match part {
+            1 => Some(self.part1()),
+            2 => Some(self.part2()),
+            _ => None,
+        }
+    }
+}
+
+struct ParsedInput<'a, S: Solution> {
+    solution: &'a S,
+    input: S::Input,
+}
+
+impl<S: Solution> Parsed for ParsedInput<'_, S> {
+    fn part1(&self) -> Answer {
+        self.solution.part1(&self.input)
+    }
+
+    fn part2(&self) -> Answer {
+        self.solution.part2(&self.input)
+    }
+}
+
+impl<S: Solution> Solver for S {
+    fn parse<'a>(&'a self, input: &str) -> Result<Box<dyn Parsed + 'a>, ParseError> {
+        let input = Solution::parse(self, input)?;
+        Ok(Box::new(ParsedInput { solution: self, input }))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::parse;
+
+    struct Sum;
+
+    impl Solution for Sum {
+        type Input = Vec<i32>;
+
+        fn parse(&self, input: &str) -> Result<Self::Input, ParseError> {
+            parse::columns(input)
+                .map(|(column, x)| parse::parse_number(1, column, x))
+                .collect()
+        }
+
+        fn part1(&self, input: &Self::Input) -> Answer {
+            input.iter().sum::<i32>().into()
+        }
+
+        fn part2(&self, input: &Self::Input) -> Answer {
+            input.iter().product::<i32>().into()
+        }
+    }
+
+    #[test]
+    fn test_solver_dispatch() {
+        let solver: &dyn Solver = &Sum;
+        let parsed = solver.parse("1 2 3 4").expect("Failed to parse");
+        assert_eq!(parsed.part(1), Some(Answer::Number(10)));
+        assert_eq!(parsed.part(2), Some(Answer::Number(24)));
+        assert_eq!(parsed.part(3), None);
+    }
+
+    #[test]
+    fn test_solver_parse_error() {
+        let solver: &dyn Solver = &Sum;
+        assert!(solver.parse("1 x").is_err());
+    }
+}
@@ -0,0 +1,371 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::str::FromStr;
+use std::{error, fmt};
+
+use common::{Answer, ParseError, ReadError, Solution};
+
+pub mod metric;
+pub mod read;
+
+use read::read_columns;
+
+/// Integer types the lists can hold, with `Default` as zero. Results are
+/// accumulated in `i128`.
+pub trait Value: Copy + Ord + Hash + Default + FromStr + Into<i128> {}
+
+impl<T: Copy + Ord + Hash + Default + FromStr + Into<i128>> Value for T {}
+
+/// How to treat lists of different lengths. The same policy applies both
+/// when reading rows with missing values and when comparing the lists.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Mismatch {
+    /// Reading: reject short rows. Comparing: refuse lists of different lengths.
+    #[default]
+    Error,
+    /// Reading: drop short rows entirely. Comparing: compare only the smallest
+    /// values, up to the length of the shorter list.
+    Skip,
+    /// Reading: fill the missing values of short rows with zeros. Comparing:
+    /// add zeros to the shorter list before sorting, as reading would.
+    Pad,
+}
+
+impl FromStr for Mismatch {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "error" => Ok(Mismatch::Error),
+            "skip" => Ok(Mismatch::Skip),
+            "pad" => Ok(Mismatch::Pad),
+            other => Err(format!("Unknown mismatch policy {}", other)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ListError {
+    /// An intermediate or total did not fit in `i128`.
+    Overflow(&'static str),
+    LengthMismatch { left: usize, right: usize },
+    /// `index` is zero-based; the message names the column counting from one.
+    MissingColumn { index: usize, columns: usize },
+}
+
+impl fmt::Display for ListError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ListError::Overflow(what) => write!(f, "arithmetic overflow computing {}", what),
+            ListError::LengthMismatch { left, right } => {
+                write!(f, "lists differ in length: {} left, {} right", left, right)
+            }
+            ListError::MissingColumn { index, columns } => {
+                write!(f, "no column {} in {} columns", index + 1, columns)
+            }
+        }
+    }
+}
+
+impl error::Error for ListError {}
+
+/// Parses two or more whitespace separated columns, returned column by column.
+fn parse_input<T: Value>(input: &str) -> Result<Vec<Vec<T>>, ParseError> {
+    match read_columns(input.as_bytes(), 2..=usize::MAX, Mismatch::Error) {
+        Ok(columns) => Ok(columns),
+        Err(ReadError::Parse(e)) => Err(e),
+        Err(ReadError::Io(e)) => unreachable!("reading from memory cannot fail: {}", e),
+    }
+}
+
+fn order_by_smallest<T: Value>(col: &[T]) -> Vec<T> {
+    let mut ordered = col.to_vec();
+    ordered.sort();
+    ordered
+}
+
+fn checked_sum(values: &[i128], what: &'static str) -> Result<i128, ListError> {
+    values
+        .iter()
+        .try_fold(0i128, |total, &value| total.checked_add(value))
+        .ok_or(ListError::Overflow(what))
+}
+
+fn calculate_differences<T: Value>(
+    left: &[T],
+    right: &[T],
+    mismatch: Mismatch,
+) -> Result<Vec<i128>, ListError> {
+    let pairs = match mismatch {
+        _ if left.len() == right.len() => left.len(),
+        Mismatch::Error => {
+            return Err(ListError::LengthMismatch { left: left.len(), right: right.len() })
+        }
+        Mismatch::Skip => left.len().min(right.len()),
+        Mismatch::Pad => left.len().max(right.len()),
+    };
+    let padded = |col: &[T]| {
+        let mut col = col.to_vec();
+        if mismatch == Mismatch::Pad {
+            col.resize(pairs, T::default());
+        }
+        order_by_smallest(&col)
+    };
+    let (left_ordered, right_ordered) = (padded(left), padded(right));
+
+    let mut difference = Vec::new();
+    for (&l, &r) in left_ordered.iter().zip(&right_ordered) {
+        let diff = l.into().checked_sub(r.into()).and_then(i128::checked_abs);
+        difference.push(diff.ok_or(ListError::Overflow("difference"))?);
+    }
+    Ok(difference)
+}
+
+fn calculate_distance<T: Value>(left: &[T], right: &[T], mismatch: Mismatch) -> Result<i128, ListError> {
+    let diffs = calculate_differences(left, right, mismatch)?;
+    checked_sum(&diffs, "distance")
+}
+
+fn frequencies<T: Value>(col: &[T]) -> HashMap<T, usize> {
+    let mut counts = HashMap::new();
+    for &value in col {
+        *counts.entry(value).or_insert(0) += 1;
+    }
+    counts
+}
+
+fn calculate_similarities<T: Value>(left: &[T], right: &[T]) -> Result<Vec<i128>, ListError> {
+    let occurrences = frequencies(right);
+    let mut similarities = Vec::new();
+    for value in left {
+        let count = occurrences.get(value).copied().unwrap_or(0) as i128;
+        let similarity = count.checked_mul((*value).into());
+        similarities.push(similarity.ok_or(ListError::Overflow("similarity"))?);
+    }
+    Ok(similarities)
+}
+
+fn calculate_similarity_score<T: Value>(left: &[T], right: &[T]) -> Result<i128, ListError> {
+    let similarities = calculate_similarities(left, right)?;
+    checked_sum(&similarities, "similarity score")
+}
+
+fn column<T>(columns: &[Vec<T>], index: usize) -> Result<&[T], ListError> {
+    columns
+        .get(index)
+        .map(Vec::as_slice)
+        .ok_or(ListError::MissingColumn { index, columns: columns.len() })
+}
+
+/// Distance between columns `a` and `b`.
+pub fn pair_distance<T: Value>(
+    columns: &[Vec<T>],
+    a: usize,
+    b: usize,
+    mismatch: Mismatch,
+) -> Result<i128, ListError> {
+    calculate_distance(column(columns, a)?, column(columns, b)?, mismatch)
+}
+
+/// Similarity score of column `a` against the occurrences in column `b`.
+pub fn pair_similarity<T: Value>(columns: &[Vec<T>], a: usize, b: usize) -> Result<i128, ListError> {
+    calculate_similarity_score(column(columns, a)?, column(columns, b)?)
+}
+
+pub const INPUT: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/input");
+
+pub struct Day1;
+
+impl Solution for Day1 {
+    type Input = Vec<Vec<i64>>;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(&self, input: &Self::Input) -> Answer {
+        pair_distance(input, 0, 1, Mismatch::Error)
+            .expect("Parsed columns have equal length and fit in i128")
+            .into()
+    }
+
+    fn part2(&self, input: &Self::Input) -> Answer {
+        pair_similarity(input, 0, 1).expect("Parsed columns fit in i128")
+            .into()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use common::random::Rng;
+    use common::ParseErrorKind;
+
+    #[test]
+    fn test_read_example() {
+        let input = r#"
+        3   4
+        4   3
+        2   5
+        1   3
+        3   9
+        3   3
+        "#;
+        let columns = parse_input::<i32>(input).expect("Failed to parse");
+        assert_eq!(columns, vec![vec![3, 4, 2, 1, 3, 3], vec![4, 3, 5, 3, 9, 3]]);
+    }
+
+    #[test]
+    fn test_empty_input() {
+        for input in ["", "\n  \n"] {
+            let parsed = Day1.parse(input).expect("Failed to parse");
+            assert_eq!(Day1.part1(&parsed), Answer::Number(0));
+            assert_eq!(Day1.part2(&parsed), Answer::Number(0));
+        }
+    }
+
+    #[test]
+    fn test_parse_missing_column() {
+        let input = "3   4\n4\n";
+        assert_eq!(
+            parse_input::<i32>(input),
+            Err(ParseError::new(2, 2, "4", ParseErrorKind::RowLength { expected: 2, found: 1 })),
+        );
+    }
+
+    #[test]
+    fn test_parse_invalid_number() {
+        let input = "3   4\n4   x3\n";
+        assert_eq!(
+            parse_input::<i32>(input),
+            Err(ParseError::new(2, 5, "x3", ParseErrorKind::InvalidNumber)),
+        );
+    }
+
+    #[test]
+    fn test_order_by_smallest() {
+        let unordered = vec![10, 9, 8, 7, 6, 5, 4, 3, 2, 1];
+        let ordered = order_by_smallest(&unordered);
+        assert_eq!(ordered, vec![1, 2, 3, 4, 5, 6, 7, 8, 9, 10]);
+    }
+
+    #[test]
+    fn test_calculate_differences() {
+        let left = vec![3, 4, 2, 1, 3, 3];
+        let right = vec![4, 3, 5, 3, 9, 3];
+        let difference = calculate_differences(&left, &right, Mismatch::Error);
+        assert_eq!(difference, Ok(vec![2, 1, 0, 1, 2, 5]));
+    }
+
+    #[test]
+    fn test_calculate_similarity_score() {
+        let left = vec![3, 4, 2, 1, 3, 3];
+        let right = vec![4, 3, 5, 3, 9, 3];
+        let score = calculate_similarity_score(&left, &right);
+        assert_eq!(score, Ok(31));
+    }
+
+    #[test]
+    fn test_calculate_similarities() {
+        let left = vec![3, 4, 2, 1, 3, 3];
+        let right = vec![4, 3, 5, 3, 9, 3];
+        let similarities = calculate_similarities(&left, &right);
+        assert_eq!(similarities, Ok(vec![9, 4, 0, 0, 9, 9]));
+    }
+
+    #[test]
+    fn test_similarities_match_pairwise_count() {
+        let mut rng = Rng::new(7);
+        let mut random = |n: usize| -> Vec<i32> { (0..n).map(|_| rng.below(50) as i32 - 10).collect() };
+        let (left, right) = (random(500), random(400));
+        let pairwise: Vec<i128> = left
+            .iter()
+            .map(|&value| right.iter().filter(|&&x| x == value).count() as i128 * value as i128)
+            .collect();
+        assert_eq!(calculate_similarities(&left, &right), Ok(pairwise));
+    }
+
+    #[test]
+    fn test_calculate_distance() {
+        let left = vec![3, 4, 2, 1, 3, 3];
+        let right = vec![4, 3, 5, 3, 9, 3];
+        assert_eq!(Ok(11), calculate_distance(&left, &right, Mismatch::Error));
+    }
+
+    #[test]
+    fn test_wide_values() {
+        let input = "3000000000   4000000000\n-3000000000   3000000000\n";
+        assert_eq!(
+            parse_input::<i32>(input),
+            Err(ParseError::new(1, 1, "3000000000", ParseErrorKind::InvalidNumber)),
+        );
+        let columns = parse_input::<i64>(input).expect("Failed to parse");
+        assert_eq!(pair_distance(&columns, 0, 1, Mismatch::Error), Ok(7_000_000_000));
+        assert_eq!(pair_similarity(&columns, 0, 1), Ok(3_000_000_000));
+
+        let (left, right) = (vec![i32::MAX, i32::MAX], vec![i32::MIN, i32::MAX]);
+        assert_eq!(calculate_distance(&left, &right, Mismatch::Error), Ok((1 << 32) - 1));
+        assert_eq!(calculate_similarity_score(&left, &right), Ok(2 * i32::MAX as i128));
+    }
+
+    #[test]
+    fn test_overflow() {
+        let (left, right) = (vec![i128::MAX], vec![-1i128]);
+        assert_eq!(calculate_distance(&left, &right, Mismatch::Error), Err(ListError::Overflow("difference")));
+
+        let (left, right) = (vec![i128::MAX, i128::MAX], vec![0, 0]);
+        assert_eq!(calculate_distance(&left, &right, Mismatch::Error), Err(ListError::Overflow("distance")));
+
+        let (left, right) = (vec![i128::MAX], vec![i128::MAX, i128::MAX]);
+        assert_eq!(calculate_similarities(&left, &right), Err(ListError::Overflow("similarity")));
+        assert_eq!(
+            ListError::Overflow("similarity").to_string(),
+            "arithmetic overflow computing similarity",
+        );
+    }
+
+    #[test]
+    fn test_mismatched_lengths() {
+        let left = vec![3, 4, 2, 1, 3, 3, 7];
+        let right = vec![4, 3, 5, 3, 9, 3];
+        assert_eq!(
+            calculate_distance(&left, &right, Mismatch::Error),
+            Err(ListError::LengthMismatch { left: 7, right: 6 }),
+        );
+        assert_eq!(
+            calculate_differences(&left, &right, Mismatch::Skip),
+            Ok(vec![2, 1, 0, 1, 2, 5]),
+        );
+        assert_eq!(
+            calculate_differences(&right, &left, Mismatch::Pad),
+            Ok(vec![1, 1, 0, 0, 1, 1, 2]),
+        );
+        // Padding the short list agrees with padding short rows while reading.
+        assert_eq!(calculate_distance(&[3, 4, 2], &[4, 3], Mismatch::Pad), Ok(2));
+    }
+
+    #[test]
+    fn test_column_pairs() {
+        let input = r#"
+        3   4   1
+        4   3   4
+        2   5   4
+        1   3   2
+        3   9   3
+        3   3   3
+        "#;
+        let columns = parse_input::<i32>(input).expect("Failed to parse");
+        assert_eq!(columns.len(), 3);
+        assert_eq!(pair_distance(&columns, 0, 1, Mismatch::Error), Ok(11));
+        assert_eq!(pair_distance(&columns, 1, 0, Mismatch::Error), Ok(11));
+        assert_eq!(pair_similarity(&columns, 0, 2), Ok(29));
+        assert_eq!(
+            pair_similarity(&columns, 0, 3),
+            Err(ListError::MissingColumn { index: 3, columns: 3 }),
+        );
+        assert_eq!(
+            ListError::MissingColumn { index: 3, columns: 3 }.to_string(),
+            "no column 4 in 3 columns",
+        );
+    }
+}
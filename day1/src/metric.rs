@@ -0,0 +1,253 @@
+use std::str::FromStr;
+
+use common::Answer;
+
+use crate::{
+    calculate_differences, calculate_distance, calculate_similarity_score, checked_sum, column,
+    frequencies, order_by_smallest, ListError, Mismatch, Value,
+};
+
+/// Ways to compare two lists, selectable by name.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Metric {
+    /// Sum of absolute differences of the sorted lists, as in part 1.
+    #[default]
+    Distance,
+    /// Each left value times its occurrences on the right, as in part 2.
+    Similarity,
+    /// Sum of squared differences of the sorted lists (L2 without the root).
+    Squared,
+    /// Largest absolute difference of the sorted lists (L∞).
+    MaxDifference,
+    /// Number of values both lists hold, counting repeats.
+    Overlap,
+    /// Shared values over all values, counting repeats.
+    Jaccard,
+    /// Earth mover's distance between the lists as distributions of equal mass.
+    EarthMovers,
+}
+
+impl Metric {
+    pub const ALL: [Metric; 7] = [
+        Metric::Distance,
+        Metric::Similarity,
+        Metric::Squared,
+        Metric::MaxDifference,
+        Metric::Overlap,
+        Metric::Jaccard,
+        Metric::EarthMovers,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Metric::Distance => "distance",
+            Metric::Similarity => "similarity",
+            Metric::Squared => "l2",
+            Metric::MaxDifference => "linf",
+            Metric::Overlap => "overlap",
+            Metric::Jaccard => "jaccard",
+            Metric::EarthMovers => "emd",
+        }
+    }
+
+    /// Compares `left` against `right`. Ratios are reported as decimal text.
+    pub fn compute<T: Value>(self, left: &[T], right: &[T], mismatch: Mismatch) -> Result<Answer, ListError> {
+        let answer = match self {
+            Metric::Distance => calculate_distance(left, right, mismatch)?.into(),
+            Metric::Similarity => calculate_similarity_score(left, right)?.into(),
+            Metric::Squared => squared_distance(left, right, mismatch)?.into(),
+            Metric::MaxDifference => max_difference(left, right, mismatch)?.into(),
+            Metric::Overlap => overlap(left, right).into(),
+            Metric::Jaccard => format!("{:.6}", jaccard(left, right)).into(),
+            Metric::EarthMovers => format!("{:.6}", earth_movers(left, right)?).into(),
+        };
+        Ok(answer)
+    }
+
+    /// Compares columns `a` and `b`.
+    pub fn compute_pair<T: Value>(
+        self,
+        columns: &[Vec<T>],
+        a: usize,
+        b: usize,
+        mismatch: Mismatch,
+    ) -> Result<Answer, ListError> {
+        self.compute(column(columns, a)?, column(columns, b)?, mismatch)
+    }
+
+    /// Compares every ordered pair of columns, row `a` holding column `a` against each other column.
+    pub fn matrix<T: Value>(self, columns: &[Vec<T>], mismatch: Mismatch) -> Result<Vec<Vec<Answer>>, ListError> {
+        (0..columns.len())
+            .map(|a| {
+                (0..columns.len())
+                    .map(|b| self.compute_pair(columns, a, b, mismatch))
+                    .collect()
+            })
+            .collect()
+    }
+}
+
+impl FromStr for Metric {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Metric::ALL
+            .into_iter()
+            .find(|metric| metric.name() == s)
+            .ok_or_else(|| format!("Unknown metric {}", s))
+    }
+}
+
+fn squared_distance<T: Value>(left: &[T], right: &[T], mismatch: Mismatch) -> Result<i128, ListError> {
+    let squares = calculate_differences(left, right, mismatch)?
+        .into_iter()
+        .map(|diff| diff.checked_mul(diff).ok_or(ListError::Overflow("squared difference")))
+        .collect::<Result<Vec<i128>, _>>()?;
+    checked_sum(&squares, "squared distance")
+}
+
+fn max_difference<T: Value>(left: &[T], right: &[T], mismatch: Mismatch) -> Result<i128, ListError> {
+    let diffs = calculate_differences(left, right, mismatch)?;
+    Ok(diffs.into_iter().max().unwrap_or(0))
+}
+
+/// Size of the multiset intersection.
+fn overlap<T: Value>(left: &[T], right: &[T]) -> usize {
+    let right_counts = frequencies(right);
+    frequencies(left)
+        .iter()
+        .map(|(value, &count)| count.min(right_counts.get(value).copied().unwrap_or(0)))
+        .sum()
+}
+
+/// Multiset Jaccard index, one for two empty lists.
+fn jaccard<T: Value>(left: &[T], right: &[T]) -> f64 {
+    let shared = overlap(left, right);
+    let union = left.len() + right.len() - shared;
+    if union == 0 {
+        1.0
+    } else {
+        shared as f64 / union as f64
+    }
+}
+
+/// Area between the cumulative distributions of the lists, each value carrying
+/// `1 / len` of its list's mass. Zero when either list is empty.
+fn earth_movers<T: Value>(left: &[T], right: &[T]) -> Result<f64, ListError> {
+    let (n, m) = (left.len() as i128, right.len() as i128);
+    if n == 0 || m == 0 {
+        return Ok(0.0);
+    }
+    let left_ordered = order_by_smallest(left);
+    let right_ordered = order_by_smallest(right);
+    let overflow = ListError::Overflow("earth mover's distance");
+
+    // Scaled by n * m so the running CDF gap stays an integer.
+    let (mut i, mut j) = (0, 0);
+    let mut total: i128 = 0;
+    let mut previous: Option<i128> = None;
+    while i < left_ordered.len() || j < right_ordered.len() {
+        let next_left = left_ordered.get(i).map(|&value| value.into());
+        let next_right = right_ordered.get(j).map(|&value| value.into());
+        let x: i128 = match (next_left, next_right) {
+            (Some(l), Some(r)) => l.min(r),
+            (Some(l), None) => l,
+            (None, Some(r)) => r,
+            (None, None) => unreachable!(),
+        };
+        if let Some(previous) = previous {
+            let gap = (i as i128 * m - j as i128 * n).abs();
+            let width = x.checked_sub(previous).ok_or(overflow)?;
+            let area = gap.checked_mul(width).ok_or(overflow)?;
+            total = total.checked_add(area).ok_or(overflow)?;
+        }
+        while left_ordered.get(i).is_some_and(|&value| value.into() == x) {
+            i += 1;
+        }
+        while right_ordered.get(j).is_some_and(|&value| value.into() == x) {
+            j += 1;
+        }
+        previous = Some(x);
+    }
+    Ok(total as f64 / (n * m) as f64)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const LEFT: [i32; 6] = [3, 4, 2, 1, 3, 3];
+    const RIGHT: [i32; 6] = [4, 3, 5, 3, 9, 3];
+
+    fn compute(metric: Metric, left: &[i32], right: &[i32]) -> Answer {
+        metric.compute(left, right, Mismatch::Pad).expect("No overflow")
+    }
+
+    #[test]
+    fn test_parse_metric() {
+        for metric in Metric::ALL {
+            assert_eq!(metric.name().parse(), Ok(metric));
+        }
+        assert!("manhattan".parse::<Metric>().is_err());
+    }
+
+    #[test]
+    fn test_metrics() {
+        assert_eq!(compute(Metric::Distance, &LEFT, &RIGHT), Answer::Number(11));
+        assert_eq!(compute(Metric::Similarity, &LEFT, &RIGHT), Answer::Number(31));
+        assert_eq!(compute(Metric::Squared, &LEFT, &RIGHT), Answer::Number(35));
+        assert_eq!(compute(Metric::MaxDifference, &LEFT, &RIGHT), Answer::Number(5));
+        assert_eq!(compute(Metric::Overlap, &LEFT, &RIGHT), Answer::Number(4));
+        assert_eq!(compute(Metric::Jaccard, &LEFT, &RIGHT), Answer::from("0.500000"));
+        // Equal lengths: the distance spread over the six units of mass.
+        assert_eq!(compute(Metric::EarthMovers, &LEFT, &RIGHT), Answer::from(format!("{:.6}", 11.0 / 6.0)));
+    }
+
+    #[test]
+    fn test_matrix() {
+        let columns = vec![LEFT.to_vec(), RIGHT.to_vec(), vec![3, 3]];
+        let matrix = Metric::Overlap.matrix(&columns, Mismatch::Error).expect("No overflow");
+        assert_eq!(matrix[0][1], Answer::Number(4));
+        assert_eq!(matrix[2][0], Answer::Number(2));
+        assert_eq!(
+            Metric::Distance.matrix(&columns, Mismatch::Error),
+            Err(ListError::LengthMismatch { left: 6, right: 2 }),
+        );
+        assert_eq!(
+            Metric::Distance.compute_pair(&columns, 0, 1, Mismatch::Error),
+            Ok(Answer::Number(11)),
+        );
+    }
+
+    #[test]
+    fn test_distance_similarity_matrices() {
+        let columns = vec![LEFT.to_vec(), RIGHT.to_vec(), vec![1, 4, 4, 2, 3, 3]];
+        let numbers = |rows: &[[i128; 3]]| -> Vec<Vec<Answer>> {
+            rows.iter().map(|row| row.iter().map(|&n| Answer::Number(n)).collect()).collect()
+        };
+        assert_eq!(
+            Metric::Distance.matrix(&columns, Mismatch::Error),
+            Ok(numbers(&[[0, 11, 1], [11, 0, 10], [1, 10, 0]])),
+        );
+        let similarities = Metric::Similarity.matrix(&columns, Mismatch::Error).expect("No overflow");
+        assert_eq!(similarities[0][1], Answer::Number(31));
+        assert_eq!(similarities[0][2], Answer::Number(29));
+        assert_eq!(similarities[0][0], Metric::Similarity.compute_pair(&columns, 0, 0, Mismatch::Error).unwrap());
+        assert_eq!(similarities[2][0], Metric::Similarity.compute_pair(&columns, 2, 0, Mismatch::Error).unwrap());
+    }
+
+    #[test]
+    fn test_earth_movers_uneven() {
+        assert_eq!(earth_movers(&[0, 2], &[1]), Ok(1.0));
+        assert_eq!(earth_movers(&[0, 0, 3], &[0]), Ok(1.0));
+        assert_eq!(earth_movers::<i32>(&[], &[1]), Ok(0.0));
+        assert_eq!(earth_movers(&[i128::MIN], &[i128::MAX]), Err(ListError::Overflow("earth mover's distance")));
+    }
+
+    #[test]
+    fn test_overlap() {
+        assert_eq!(overlap(&[1, 1, 1, 2], &[1, 1, 3]), 2);
+        assert_eq!(jaccard::<i32>(&[], &[]), 1.0);
+        assert_eq!(jaccard(&[1, 2], &[3]), 0.0);
+    }
+}
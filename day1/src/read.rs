@@ -0,0 +1,120 @@
+use std::io::BufRead;
+use std::ops::RangeInclusive;
+
+use common::{parse, ParseError, ParseErrorKind, ReadError};
+
+use crate::{Mismatch, Value};
+
+/// Streams whitespace separated columns from `reader` one line at a time.
+/// The first row fixes the number of columns, which must lie in `widths`.
+/// Input without rows gives the narrowest allowed width of empty columns.
+/// Blank lines are ignored, longer rows and non-numeric values are errors.
+/// Shorter rows are handled by `mismatch`: `Error` reports them, `Skip` drops
+/// them and `Pad` fills their missing values with zeros.
+pub fn read_columns<T: Value, R: BufRead>(
+    mut reader: R,
+    widths: RangeInclusive<usize>,
+    mismatch: Mismatch,
+) -> Result<Vec<Vec<T>>, ReadError> {
+    let mut columns: Vec<Vec<T>> = Vec::new();
+    let mut line = String::new();
+    let mut line_number = 0;
+    loop {
+        line.clear();
+        if reader.read_line(&mut line)? == 0 {
+            break;
+        }
+        line_number += 1;
+        let fields: Vec<(usize, &str)> = parse::columns(&line).collect();
+        if fields.is_empty() {
+            continue;
+        }
+        if columns.is_empty() {
+            let width = fields.len().clamp(*widths.start(), *widths.end());
+            columns.resize_with(width, Vec::new);
+        }
+        let width = columns.len();
+        if fields.len() > width {
+            let (extra_col, _) = fields[width];
+            let kind = ParseErrorKind::RowLength { expected: width, found: fields.len() };
+            return Err(ParseError::new(line_number, extra_col, line.trim(), kind).into());
+        }
+        let values = fields
+            .iter()
+            .map(|&(col, text)| parse::parse_number(line_number, col, text))
+            .collect::<Result<Vec<T>, _>>()?;
+        if values.len() < width {
+            match mismatch {
+                Mismatch::Error => {
+                    let kind = ParseErrorKind::RowLength { expected: width, found: values.len() };
+                    let end = line.trim_end().chars().count() + 1;
+                    return Err(ParseError::new(line_number, end, line.trim(), kind).into());
+                }
+                Mismatch::Skip => continue,
+                Mismatch::Pad => {}
+            }
+        }
+        let padded = values.into_iter().chain(std::iter::repeat(T::default()));
+        for (column, value) in columns.iter_mut().zip(padded) {
+            column.push(value);
+        }
+    }
+    if columns.is_empty() {
+        columns.resize_with(*widths.start(), Vec::new);
+    }
+    Ok(columns)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_read_two_columns() {
+        let input = "3   4\n\n4   3\n2\n";
+        let read = |mismatch| read_columns::<i32, _>(input.as_bytes(), 2..=2, mismatch);
+        assert_eq!(read(Mismatch::Skip).unwrap(), vec![vec![3, 4], vec![4, 3]]);
+        assert_eq!(read(Mismatch::Pad).unwrap(), vec![vec![3, 4, 2], vec![4, 3, 0]]);
+        match read(Mismatch::Error) {
+            Err(ReadError::Parse(e)) => {
+                assert_eq!(
+                    e,
+                    ParseError::new(4, 2, "2", ParseErrorKind::RowLength { expected: 2, found: 1 }),
+                )
+            }
+            other => panic!("Expected a parse error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_read_columns() {
+        let input = "1 2 3\n4 5 6\n7 8\n";
+        let read = |mismatch| read_columns::<i32, _>(input.as_bytes(), 2..=usize::MAX, mismatch);
+        assert_eq!(read(Mismatch::Skip).unwrap(), vec![vec![1, 4], vec![2, 5], vec![3, 6]]);
+        assert_eq!(read(Mismatch::Pad).unwrap(), vec![vec![1, 4, 7], vec![2, 5, 8], vec![3, 6, 0]]);
+        assert!(read(Mismatch::Error).is_err());
+
+        let empty = read_columns::<i32, _>(" \n\n".as_bytes(), 2..=usize::MAX, Mismatch::Error).unwrap();
+        assert_eq!(empty, vec![Vec::<i32>::new(), Vec::new()]);
+
+        let narrow = "1\n2\n";
+        let error = read_columns::<i32, _>(narrow.as_bytes(), 2..=usize::MAX, Mismatch::Error).unwrap_err();
+        assert_eq!(error.to_string(), "line 1, column 2: expected 2 columns, found 1 in `1`");
+    }
+
+    #[test]
+    fn test_read_bad_rows() {
+        let three = "3   4\n4   3   7\n";
+        match read_columns::<i32, _>(three.as_bytes(), 2..=2, Mismatch::Skip) {
+            Err(ReadError::Parse(e)) => assert_eq!(
+                e,
+                ParseError::new(2, 9, "4   3   7", ParseErrorKind::RowLength { expected: 2, found: 3 }),
+            ),
+            other => panic!("Expected a parse error, got {:?}", other),
+        }
+
+        let text = "3   4\n4   3\nfour   3\n";
+        let error = read_columns::<i32, _>(text.as_bytes(), 2..=2, Mismatch::Pad).unwrap_err();
+        assert_eq!(error.to_string(), "line 3, column 1: invalid number in `four`");
+    }
+}
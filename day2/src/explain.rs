@@ -0,0 +1,292 @@
+use std::fmt;
+
+use crate::{first_bad_step, longest_safe_subsequence, step, SafetyPolicy};
+
+/// Why a step between two adjacent levels is not allowed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Failure {
+    /// The levels move against the direction of the report.
+    DirectionChange,
+    /// The levels are equal and the policy does not allow it.
+    ZeroStep,
+    /// The levels move by less than the minimum step.
+    TooSmall,
+    /// The levels move by more than the maximum step.
+    TooLarge,
+}
+
+impl fmt::Display for Failure {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Failure::DirectionChange => write!(f, "direction change"),
+            Failure::ZeroStep => write!(f, "zero step"),
+            Failure::TooSmall => write!(f, "step too small"),
+            Failure::TooLarge => write!(f, "step too large"),
+        }
+    }
+}
+
+/// The first offending pair of levels, by index into the report.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Violation {
+    pub pair: (usize, usize),
+    pub levels: (i32, i32),
+    pub failure: Failure,
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} at indices {}-{} ({} -> {})",
+            self.failure, self.pair.0, self.pair.1, self.levels.0, self.levels.1
+        )
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Verdict {
+    Safe,
+    /// Unsafe as is, but safe once the levels at `removed` are dropped. One of
+    /// them is always a level of `violation`.
+    Dampened { removed: Vec<usize>, violation: Violation },
+    Unsafe(Violation),
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Verdict::Safe => write!(f, "safe"),
+            Verdict::Dampened { removed, violation } => {
+                let indices: Vec<String> = removed.iter().map(|index| index.to_string()).collect();
+                let noun = if removed.len() == 1 { "index" } else { "indices" };
+                write!(f, "safe after removing {} {}, {}", noun, indices.join(", "), violation)
+            }
+            Verdict::Unsafe(violation) => write!(f, "unsafe, {}", violation),
+        }
+    }
+}
+
+fn classify(policy: &SafetyPolicy, a: i32, b: i32, increasing: bool) -> Failure {
+    let diff = step(a, b, increasing);
+    if diff == 0 {
+        Failure::ZeroStep
+    } else if diff < 0 {
+        Failure::DirectionChange
+    } else if diff < i64::from(policy.min_step) {
+        Failure::TooSmall
+    } else {
+        Failure::TooLarge
+    }
+}
+
+/// Explains whether the report is safe, safe with up to `tolerance` levels
+/// removed, or unsafe, agreeing with `safe_within`. With several allowed
+/// directions, the reported violation comes from the direction the report
+/// follows longest, preferring one the failing step itself agrees with.
+pub fn explain(row: &[i32], policy: &SafetyPolicy, tolerance: usize) -> Verdict {
+    let mut attempts = Vec::new();
+    for &increasing in policy.directions() {
+        let Some((a, b)) = first_bad_step(row, policy, None, increasing) else {
+            return Verdict::Safe;
+        };
+        let violation = Violation {
+            pair: (a, b),
+            levels: (row[a], row[b]),
+            failure: classify(policy, row[a], row[b], increasing),
+        };
+        attempts.push((increasing, violation));
+    }
+    attempts.sort_by_key(|(_, violation)| {
+        (std::cmp::Reverse(violation.pair.1), violation.failure == Failure::DirectionChange)
+    });
+    if tolerance == 0 {
+        return Verdict::Unsafe(attempts[0].1);
+    }
+
+    for &(increasing, violation) in &attempts {
+        let (a, b) = violation.pair;
+        if let Some(removed) = [a, b]
+            .into_iter()
+            .find(|&skip| first_bad_step(row, policy, Some(skip), increasing).is_none())
+        {
+            return Verdict::Dampened { removed: vec![removed], violation };
+        }
+    }
+    if tolerance > 1 {
+        // Any safe subsequence in a direction drops a level of that
+        // direction's first bad step, so the violation still matches.
+        let best = attempts
+            .iter()
+            .map(|&(increasing, violation)| (longest_safe_subsequence(row, policy, increasing), violation))
+            .min_by_key(|(kept, _)| row.len() - kept.len());
+        if let Some((kept, violation)) = best {
+            if row.len() - kept.len() <= tolerance {
+                let removed = (0..row.len()).filter(|i| !kept.contains(i)).collect();
+                return Verdict::Dampened { removed, violation };
+            }
+        }
+    }
+    Verdict::Unsafe(attempts[0].1)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::random;
+    use crate::{check_row, safe_within, Direction};
+    use common::random::Rng;
+
+    fn violation(pair: (usize, usize), levels: (i32, i32), failure: Failure) -> Violation {
+        Violation { pair, levels, failure }
+    }
+
+    #[test]
+    fn test_explain_example() {
+        let policy = SafetyPolicy::default();
+        assert_eq!(explain(&[7, 6, 4, 2, 1], &policy, 1), Verdict::Safe);
+        assert_eq!(
+            explain(&[1, 2, 7, 8, 9], &policy, 1),
+            Verdict::Unsafe(violation((1, 2), (2, 7), Failure::TooLarge)),
+        );
+        assert_eq!(
+            explain(&[9, 7, 6, 2, 1], &policy, 1),
+            Verdict::Unsafe(violation((2, 3), (6, 2), Failure::TooLarge)),
+        );
+        assert_eq!(
+            explain(&[1, 3, 2, 4, 5], &policy, 1),
+            Verdict::Dampened {
+                removed: vec![1],
+                violation: violation((1, 2), (3, 2), Failure::DirectionChange),
+            },
+        );
+        assert_eq!(
+            explain(&[8, 6, 4, 4, 1], &policy, 1),
+            Verdict::Dampened {
+                removed: vec![2],
+                violation: violation((2, 3), (4, 4), Failure::ZeroStep),
+            },
+        );
+        assert_eq!(explain(&[1, 3, 6, 7, 9], &policy, 1), Verdict::Safe);
+    }
+
+    #[test]
+    fn test_explain_removes_from_violation() {
+        assert_eq!(
+            explain(&[0, 2, 1, 0], &SafetyPolicy::default(), 1),
+            Verdict::Dampened {
+                removed: vec![0],
+                violation: violation((0, 1), (0, 2), Failure::DirectionChange),
+            },
+        );
+    }
+
+    #[test]
+    fn test_explain_too_small() {
+        let policy = SafetyPolicy { min_step: 2, ..SafetyPolicy::default() };
+        assert_eq!(
+            explain(&[1, 3, 4, 5, 6], &policy, 1),
+            Verdict::Unsafe(violation((1, 2), (3, 4), Failure::TooSmall)),
+        );
+        let loose = SafetyPolicy { min_step: -3, direction: Direction::Increasing, ..SafetyPolicy::default() };
+        assert_eq!(
+            explain(&[1, 1, 0], &loose, 0),
+            Verdict::Unsafe(violation((0, 1), (1, 1), Failure::ZeroStep)),
+        );
+        assert_eq!(
+            explain(&[1, 0], &loose, 0),
+            Verdict::Unsafe(violation((0, 1), (1, 0), Failure::DirectionChange)),
+        );
+    }
+
+    #[test]
+    fn test_explain_matches_checks() {
+        let policy = SafetyPolicy::default();
+        let mut rng = Rng::new(5);
+        for _ in 0..5_000 {
+            let row = random::row(&mut rng, 0..8, 5);
+            for tolerance in 0..4 {
+                let verdict = explain(&row, &policy, tolerance);
+                assert_eq!(verdict == Verdict::Safe, check_row(&row, &policy), "row {:?}", row);
+                assert_eq!(
+                    !matches!(verdict, Verdict::Unsafe(_)),
+                    safe_within(&row, &policy, tolerance),
+                    "row {:?}, tolerance {}",
+                    row,
+                    tolerance,
+                );
+                if let Verdict::Dampened { removed, violation } = verdict {
+                    assert!(removed.len() <= tolerance, "row {:?}", row);
+                    assert!(
+                        removed.contains(&violation.pair.0) || removed.contains(&violation.pair.1),
+                        "row {:?}",
+                        row,
+                    );
+                    let fixed: Vec<i32> = row
+                        .iter()
+                        .enumerate()
+                        .filter(|(i, _)| !removed.contains(i))
+                        .map(|(_, &level)| level)
+                        .collect();
+                    assert!(check_row(&fixed, &policy), "row {:?}", row);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_explain_tolerance() {
+        let policy = SafetyPolicy::default();
+        assert_eq!(
+            explain(&[1, 3, 2, 4, 5], &policy, 0),
+            Verdict::Unsafe(violation((1, 2), (3, 2), Failure::DirectionChange)),
+        );
+        assert_eq!(
+            explain(&[1, 2, 7, 8, 9], &policy, 2),
+            Verdict::Dampened {
+                removed: vec![0, 1],
+                violation: violation((1, 2), (2, 7), Failure::TooLarge),
+            },
+        );
+        assert_eq!(
+            explain(&[1, 2, 7, 8, 9], &policy, 3),
+            explain(&[1, 2, 7, 8, 9], &policy, 2),
+        );
+    }
+
+    #[test]
+    fn test_explain_extreme_levels() {
+        let policy = SafetyPolicy::default();
+        assert_eq!(
+            explain(&[i32::MAX, i32::MIN, i32::MAX - 1], &policy, 1),
+            Verdict::Dampened {
+                removed: vec![1],
+                violation: violation((0, 1), (i32::MAX, i32::MIN), Failure::TooLarge),
+            },
+        );
+    }
+
+    #[test]
+    fn test_display() {
+        let verdict = Verdict::Dampened {
+            removed: vec![1],
+            violation: violation((1, 2), (3, 2), Failure::DirectionChange),
+        };
+        assert_eq!(
+            verdict.to_string(),
+            "safe after removing index 1, direction change at indices 1-2 (3 -> 2)",
+        );
+        assert_eq!(
+            Verdict::Unsafe(violation((1, 2), (2, 7), Failure::TooLarge)).to_string(),
+            "unsafe, step too large at indices 1-2 (2 -> 7)",
+        );
+        let verdict = Verdict::Dampened {
+            removed: vec![3, 4],
+            violation: violation((1, 2), (2, 7), Failure::TooLarge),
+        };
+        assert_eq!(
+            verdict.to_string(),
+            "safe after removing indices 3, 4, step too large at indices 1-2 (2 -> 7)",
+        );
+    }
+}
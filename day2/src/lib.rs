@@ -0,0 +1,439 @@
+use std::{error, fmt};
+
+use common::{parse, Answer, ParseError, Solution};
+
+pub mod explain;
+#[cfg(test)]
+mod random;
+pub mod stream;
+
+/// Parses one report, `None` for a blank line.
+fn parse_row(line_number: usize, line: &str) -> Result<Option<Vec<i32>>, ParseError> {
+    if line.trim().is_empty() {
+        return Ok(None);
+    }
+    let row = parse::columns(line)
+        .map(|(column, x)| parse::parse_number(line_number, column, x))
+        .collect::<Result<Vec<i32>, ParseError>>()?;
+    Ok(Some(row))
+}
+
+fn parse_input(input: &str) -> Result<Vec<Vec<i32>>, ParseError> {
+    let mut rows = Vec::new();
+    for (index, line) in input.lines().enumerate() {
+        if let Some(row) = parse_row(index + 1, line)? {
+            rows.push(row);
+        }
+    }
+    Ok(rows)
+}
+
+/// Which way the levels of a safe report must move.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Direction {
+    Increasing,
+    Decreasing,
+    /// Either way, as long as the whole report agrees.
+    #[default]
+    Either,
+}
+
+/// Rules a report must follow to be safe. The default is the puzzle's:
+/// strictly increasing or decreasing by 1 to 3 per step.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SafetyPolicy {
+    /// Smallest allowed step, treated as 1 when lower: equal levels are only
+    /// allowed through `allow_equal` and steps never go against `direction`.
+    pub min_step: i32,
+    pub max_step: i32,
+    /// Adjacent equal levels are fine regardless of `min_step`.
+    pub allow_equal: bool,
+    pub direction: Direction,
+}
+
+/// A step range no report could satisfy as intended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PolicyError {
+    pub min_step: i32,
+    pub max_step: i32,
+}
+
+impl fmt::Display for PolicyError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "invalid step range {} to {}, expected 1 <= min <= max",
+            self.min_step, self.max_step
+        )
+    }
+}
+
+impl error::Error for PolicyError {}
+
+impl Default for SafetyPolicy {
+    fn default() -> Self {
+        SafetyPolicy {
+            min_step: 1,
+            max_step: 3,
+            allow_equal: false,
+            direction: Direction::Either,
+        }
+    }
+}
+
+impl SafetyPolicy {
+    /// A policy allowing steps of `min_step` to `max_step`, which must satisfy
+    /// `1 <= min_step <= max_step`.
+    pub fn new(
+        min_step: i32,
+        max_step: i32,
+        allow_equal: bool,
+        direction: Direction,
+    ) -> Result<Self, PolicyError> {
+        if min_step < 1 || min_step > max_step {
+            return Err(PolicyError { min_step, max_step });
+        }
+        Ok(SafetyPolicy { min_step, max_step, allow_equal, direction })
+    }
+
+    /// The directions to try, `true` meaning increasing.
+    fn directions(&self) -> &'static [bool] {
+        match self.direction {
+            Direction::Increasing => &[true],
+            Direction::Decreasing => &[false],
+            Direction::Either => &[true, false],
+        }
+    }
+
+    fn step_ok(&self, a: i32, b: i32, increasing: bool) -> bool {
+        let diff = step(a, b, increasing);
+        if diff == 0 {
+            return self.allow_equal;
+        }
+        (i64::from(self.min_step.max(1))..=i64::from(self.max_step)).contains(&diff)
+    }
+}
+
+/// Change from `a` to `b` in the given direction, widened so that any pair
+/// of levels fits.
+fn step(a: i32, b: i32, increasing: bool) -> i64 {
+    let (a, b) = (i64::from(a), i64::from(b));
+    if increasing { b - a } else { a - b }
+}
+
+/// First adjacent pair of levels, ignoring the level at `skip`, that breaks
+/// the rules for the given direction.
+fn first_bad_step(
+    row: &[i32],
+    policy: &SafetyPolicy,
+    skip: Option<usize>,
+    increasing: bool,
+) -> Option<(usize, usize)> {
+    let mut levels = row.iter().copied().enumerate().filter(|&(i, _)| Some(i) != skip);
+    let (mut prev, mut prev_level) = levels.next()?;
+    for (i, level) in levels {
+        if !policy.step_ok(prev_level, level, increasing) {
+            return Some((prev, i));
+        }
+        (prev, prev_level) = (i, level);
+    }
+    None
+}
+
+pub fn check_row(row: &[i32], policy: &SafetyPolicy) -> bool {
+    policy
+        .directions()
+        .iter()
+        .any(|&increasing| first_bad_step(row, policy, None, increasing).is_none())
+}
+
+/// Safe with at most one level removed. Any fix has to remove one of the two
+/// levels of the first bad step, so only those two are retried, in O(n).
+pub fn safe_row(row: &[i32], policy: &SafetyPolicy) -> bool {
+    policy.directions().iter().any(|&increasing| {
+        match first_bad_step(row, policy, None, increasing) {
+            None => true,
+            Some((a, b)) => [a, b]
+                .into_iter()
+                .any(|skip| first_bad_step(row, policy, Some(skip), increasing).is_none()),
+        }
+    })
+}
+
+/// Indices of a longest subsequence of levels that is safe in the given
+/// direction. O(n²) per report.
+fn longest_safe_subsequence(row: &[i32], policy: &SafetyPolicy, increasing: bool) -> Vec<usize> {
+    // ending_at[i]: length of the longest safe subsequence ending with level i,
+    // and the level before i in it.
+    let mut ending_at: Vec<(usize, Option<usize>)> = Vec::with_capacity(row.len());
+    for (i, &level) in row.iter().enumerate() {
+        let best = (0..i)
+            .filter(|&j| policy.step_ok(row[j], level, increasing))
+            .max_by_key(|&j| ending_at[j].0);
+        ending_at.push((best.map_or(0, |j| ending_at[j].0) + 1, best));
+    }
+    let mut kept = Vec::new();
+    let mut last = (0..row.len()).max_by_key(|&i| ending_at[i].0);
+    while let Some(i) = last {
+        kept.push(i);
+        last = ending_at[i].1;
+    }
+    kept.reverse();
+    kept
+}
+
+/// Fewest levels to remove for the report to become safe, from the longest
+/// safe subsequence in any allowed direction. O(n²) per report.
+pub fn min_removals(row: &[i32], policy: &SafetyPolicy) -> usize {
+    let longest = policy
+        .directions()
+        .iter()
+        .map(|&increasing| longest_safe_subsequence(row, policy, increasing).len())
+        .max()
+        .unwrap_or(0);
+    row.len() - longest
+}
+
+/// Safe with at most `tolerance` levels removed.
+pub fn safe_within(row: &[i32], policy: &SafetyPolicy, tolerance: usize) -> bool {
+    match tolerance {
+        0 => check_row(row, policy),
+        1 => safe_row(row, policy),
+        _ => min_removals(row, policy) <= tolerance,
+    }
+}
+
+fn num_strictly_safe_rows(rows: &[Vec<i32>], policy: &SafetyPolicy) -> i32 {
+    rows.iter().filter(|&row| check_row(row, policy)).count() as i32
+}
+
+pub fn num_safe_rows(rows: &[Vec<i32>], policy: &SafetyPolicy, tolerance: usize) -> i32 {
+    rows.iter().filter(|&row| safe_within(row, policy, tolerance)).count() as i32
+}
+
+pub const INPUT: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/input");
+
+pub struct Day2;
+
+impl Solution for Day2 {
+    type Input = Vec<Vec<i32>>;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(&self, input: &Self::Input) -> Answer {
+        num_strictly_safe_rows(input, &SafetyPolicy::default()).into()
+    }
+
+    fn part2(&self, input: &Self::Input) -> Answer {
+        num_safe_rows(input, &SafetyPolicy::default(), 1).into()
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::bool_assert_comparison, clippy::useless_vec)]
+mod test {
+    use super::*;
+    use crate::random;
+    use common::random::Rng;
+    use common::ParseErrorKind;
+
+    const DEFAULT: SafetyPolicy = SafetyPolicy {
+        min_step: 1,
+        max_step: 3,
+        allow_equal: false,
+        direction: Direction::Either,
+    };
+
+    #[test]
+    fn test_parse_input() {
+        let input = r#"
+        7 6 4 2 1
+        1 2 7 8 9
+        9 7 6 2 1
+        1 3 2 4 5
+        8 6 4 4 1
+        1 3 6 7 9
+        "#;
+
+        let rows = parse_input(input).expect("Failed to parse");
+        assert_eq!(
+            rows,
+            vec![
+                vec![7, 6, 4, 2, 1],
+                vec![1, 2, 7, 8, 9],
+                vec![9, 7, 6, 2, 1],
+                vec![1, 3, 2, 4, 5],
+                vec![8, 6, 4, 4, 1],
+                vec![1, 3, 6, 7, 9],
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_invalid_number() {
+        let input = "7 6 4 2 1\n1 2 7 8 9\n9 7 six 2 1\n";
+        assert_eq!(
+            parse_input(input),
+            Err(ParseError::new(3, 5, "six", ParseErrorKind::InvalidNumber)),
+        );
+    }
+
+    #[test]
+    fn test_num_safe_rows() {
+        let rows = vec![
+            vec![7, 6, 4, 2, 1],
+            vec![1, 2, 7, 8, 9],
+            vec![9, 7, 6, 2, 1],
+            vec![1, 3, 2, 4, 5],
+            vec![8, 6, 4, 4, 1],
+            vec![1, 3, 6, 7, 9],
+        ];
+        assert_eq!(num_safe_rows(&rows, &DEFAULT, 1), 4);
+        assert_eq!(num_safe_rows(&rows, &DEFAULT, 0), num_strictly_safe_rows(&rows, &DEFAULT));
+        assert_eq!(num_safe_rows(&rows, &DEFAULT, 2), 6);
+    }
+
+    #[test]
+    fn test_num_strictly_safe_rows() {
+        let rows = vec![
+            vec![7, 6, 4, 2, 1],
+            vec![1, 2, 7, 8, 9],
+            vec![9, 7, 6, 2, 1],
+            vec![1, 3, 2, 4, 5],
+            vec![8, 6, 4, 4, 1],
+            vec![1, 3, 6, 7, 9],
+        ];
+        assert_eq!(num_strictly_safe_rows(&rows, &DEFAULT), 2);
+    }
+
+    fn filter_out_index(row: &[i32], idx: usize) -> Vec<i32> {
+        row.iter()
+            .enumerate()
+            .filter(|(i, _)| *i != idx)
+            .map(|(_, x)| *x)
+            .collect()
+    }
+
+    /// The original dampener: retry the check with each level removed in turn.
+    fn safe_row_by_removal(row: &[i32]) -> bool {
+        check_row(row, &DEFAULT)
+            || (0..row.len()).any(|i| check_row(&filter_out_index(row, i), &DEFAULT))
+    }
+
+    #[test]
+    fn test_safe_row_matches_removal() {
+        let mut rng = Rng::new(2);
+        for _ in 0..20_000 {
+            let row = random::row(&mut rng, 0..9, 5);
+            assert_eq!(safe_row(&row, &DEFAULT), safe_row_by_removal(&row), "row {:?}", row);
+        }
+    }
+
+    #[test]
+    fn test_min_removals() {
+        assert_eq!(min_removals(&[7, 6, 4, 2, 1], &DEFAULT), 0);
+        assert_eq!(min_removals(&[1, 3, 2, 4, 5], &DEFAULT), 1);
+        assert_eq!(min_removals(&[1, 2, 7, 8, 9], &DEFAULT), 2);
+        assert_eq!(min_removals(&[9, 7, 6, 2, 1], &DEFAULT), 2);
+        assert_eq!(min_removals(&[1, 1, 1, 1], &DEFAULT), 3);
+        assert_eq!(min_removals(&[], &DEFAULT), 0);
+    }
+
+    #[test]
+    fn test_min_removals_matches_dampener() {
+        let mut rng = Rng::new(3);
+        for _ in 0..5_000 {
+            let row = random::row(&mut rng, 0..8, 5);
+            assert_eq!(min_removals(&row, &DEFAULT) == 0, check_row(&row, &DEFAULT), "row {:?}", row);
+            assert_eq!(min_removals(&row, &DEFAULT) <= 1, safe_row(&row, &DEFAULT), "row {:?}", row);
+        }
+    }
+
+    #[test]
+    fn test_default_policy() {
+        assert_eq!(SafetyPolicy::default(), DEFAULT);
+    }
+
+    #[test]
+    fn test_custom_policy() {
+        let increasing = SafetyPolicy { direction: Direction::Increasing, ..DEFAULT };
+        assert!(check_row(&[1, 3, 6, 7, 9], &increasing));
+        assert!(!check_row(&[7, 6, 4, 2, 1], &increasing));
+        assert!(safe_row(&[1, 3, 2, 4, 5], &increasing));
+        assert!(!safe_row(&[5, 4, 3, 2], &increasing));
+        assert_eq!(min_removals(&[5, 4, 3, 2], &increasing), 3);
+
+        let decreasing = SafetyPolicy { direction: Direction::Decreasing, ..DEFAULT };
+        assert!(check_row(&[7, 6, 4, 2, 1], &decreasing));
+        assert!(!check_row(&[1, 3, 6, 7, 9], &decreasing));
+
+        let flat = SafetyPolicy { allow_equal: true, ..DEFAULT };
+        assert!(check_row(&[8, 6, 4, 4, 1], &flat));
+        assert!(check_row(&[2, 2, 2], &flat));
+        assert!(!check_row(&[1, 2, 2, 1], &flat));
+
+        let wide = SafetyPolicy { min_step: 2, max_step: 5, ..DEFAULT };
+        assert!(check_row(&[1, 3, 8, 10], &wide));
+        assert!(!check_row(&[1, 2, 3], &wide));
+        assert_eq!(min_removals(&[1, 2, 3], &wide), 1);
+    }
+
+    #[test]
+    fn test_new_policy() {
+        assert_eq!(SafetyPolicy::new(1, 3, false, Direction::Either), Ok(DEFAULT));
+        assert_eq!(SafetyPolicy::new(2, 2, true, Direction::Increasing).map(|p| p.min_step), Ok(2));
+        for (min_step, max_step) in [(0, 3), (-2, 3), (3, 1)] {
+            assert_eq!(
+                SafetyPolicy::new(min_step, max_step, false, Direction::Either),
+                Err(PolicyError { min_step, max_step }),
+            );
+        }
+        assert_eq!(
+            PolicyError { min_step: 3, max_step: 1 }.to_string(),
+            "invalid step range 3 to 1, expected 1 <= min <= max",
+        );
+    }
+
+    #[test]
+    fn test_unvalidated_policy() {
+        // Equal levels need `allow_equal` even with a zero minimum.
+        let zero = SafetyPolicy { min_step: 0, ..DEFAULT };
+        assert!(!check_row(&[1, 1, 2], &zero));
+        assert!(check_row(&[1, 2, 3], &zero));
+
+        // A negative minimum does not let steps go the wrong way.
+        let negative = SafetyPolicy { min_step: -3, direction: Direction::Increasing, ..DEFAULT };
+        assert!(!check_row(&[3, 2, 1], &negative));
+        assert!(check_row(&[1, 2, 5], &negative));
+
+        let empty = SafetyPolicy { min_step: 3, max_step: 1, ..DEFAULT };
+        assert!(!check_row(&[1, 2], &empty));
+    }
+
+    #[test]
+    fn test_extreme_levels() {
+        let rows = parse_input("2147483647 -2147483648\n-2147483648 2147483647 2147483646\n").unwrap();
+        assert_eq!(num_strictly_safe_rows(&rows, &DEFAULT), 0);
+        assert_eq!(num_safe_rows(&rows, &DEFAULT, 1), 2);
+        let huge = SafetyPolicy { min_step: 1, max_step: i32::MAX, ..DEFAULT };
+        assert!(!check_row(&[i32::MIN, i32::MAX], &huge));
+        assert!(check_row(&[-1, i32::MAX - 1], &huge));
+    }
+
+    #[test]
+    fn test_safe_row() {
+        assert_eq!(safe_row(&vec![7, 6, 4, 2, 1], &DEFAULT), true);
+        assert_eq!(safe_row(&vec![1, 2, 7, 8, 9], &DEFAULT), false);
+        assert_eq!(safe_row(&vec![9, 7, 6, 2, 1], &DEFAULT), false);
+        assert_eq!(safe_row(&vec![1, 3, 2, 4, 5], &DEFAULT), true);
+        assert_eq!(safe_row(&vec![8, 6, 4, 4, 1], &DEFAULT), true);
+        assert_eq!(safe_row(&vec![1, 3, 6, 7, 9], &DEFAULT), true);
+        assert_eq!(safe_row(&vec![], &DEFAULT), true);
+        assert_eq!(safe_row(&vec![5, 5], &DEFAULT), true);
+        assert_eq!(safe_row(&vec![9, 1, 2, 3], &DEFAULT), true);
+        assert_eq!(safe_row(&vec![1, 2, 3, 9], &DEFAULT), true);
+        assert_eq!(safe_row(&vec![1, 2, 1, 2], &DEFAULT), false);
+    }
+}
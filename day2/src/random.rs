@@ -0,0 +1,16 @@
+use std::ops::Range;
+
+use common::random::Rng;
+
+/// A report with a length in `lengths` whose levels move by at most
+/// `max_step` either way, so that safe and nearly safe reports are common.
+pub fn row(rng: &mut Rng, lengths: Range<usize>, max_step: i32) -> Vec<i32> {
+    let len = lengths.start + rng.below((lengths.end - lengths.start) as u64) as usize;
+    let mut level = rng.below(50) as i32;
+    let mut row = Vec::with_capacity(len);
+    for _ in 0..len {
+        row.push(level);
+        level += rng.below(2 * max_step as u64 + 1) as i32 - max_step;
+    }
+    row
+}
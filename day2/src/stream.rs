@@ -0,0 +1,211 @@
+use std::io::BufRead;
+use std::ops::AddAssign;
+use std::thread;
+
+use common::{ParseError, ReadError};
+
+use crate::{check_row, parse_row, safe_within, SafetyPolicy};
+
+/// Lines each thread classifies per batch, bounding what is held in memory.
+const BATCH_LINES: usize = 4096;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Class {
+    Safe,
+    /// Safe only once some levels are removed.
+    Dampened,
+    Unsafe,
+}
+
+/// Classifies a report, allowing up to `tolerance` removed levels.
+pub fn classify(row: &[i32], policy: &SafetyPolicy, tolerance: usize) -> Class {
+    if check_row(row, policy) {
+        Class::Safe
+    } else if tolerance > 0 && safe_within(row, policy, tolerance) {
+        Class::Dampened
+    } else {
+        Class::Unsafe
+    }
+}
+
+/// Number of reports in each class.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Tally {
+    pub safe: usize,
+    pub dampened: usize,
+    pub failed: usize,
+}
+
+impl Tally {
+    pub fn add(&mut self, class: Class) {
+        match class {
+            Class::Safe => self.safe += 1,
+            Class::Dampened => self.dampened += 1,
+            Class::Unsafe => self.failed += 1,
+        }
+    }
+
+    pub fn total(&self) -> usize {
+        self.safe + self.dampened + self.failed
+    }
+}
+
+impl AddAssign for Tally {
+    fn add_assign(&mut self, other: Tally) {
+        self.safe += other.safe;
+        self.dampened += other.dampened;
+        self.failed += other.failed;
+    }
+}
+
+fn classify_lines(
+    lines: &[String],
+    first_line: usize,
+    policy: &SafetyPolicy,
+    tolerance: usize,
+) -> Result<Tally, ParseError> {
+    let mut tally = Tally::default();
+    for (offset, line) in lines.iter().enumerate() {
+        if let Some(row) = parse_row(first_line + offset, line)? {
+            tally.add(classify(&row, policy, tolerance));
+        }
+    }
+    Ok(tally)
+}
+
+/// Splits the batch into one chunk per thread. On bad input the error from
+/// the earliest chunk wins, so it is the first bad line of the batch.
+fn classify_batch(
+    batch: &[String],
+    first_line: usize,
+    policy: &SafetyPolicy,
+    tolerance: usize,
+    threads: usize,
+) -> Result<Tally, ParseError> {
+    if threads == 1 || batch.is_empty() {
+        return classify_lines(batch, first_line, policy, tolerance);
+    }
+    let chunk = batch.len().div_ceil(threads);
+    thread::scope(|scope| {
+        let workers: Vec<_> = batch
+            .chunks(chunk)
+            .enumerate()
+            .map(|(i, lines)| {
+                scope.spawn(move || classify_lines(lines, first_line + i * chunk, policy, tolerance))
+            })
+            .collect();
+        let mut tally = Tally::default();
+        for worker in workers {
+            tally += worker.join().expect("Classifier thread panicked")?;
+        }
+        Ok(tally)
+    })
+}
+
+/// Classifies reports as they are read, `threads` batches of lines at a time,
+/// so memory stays bounded however long the input is.
+pub fn classify_stream<R: BufRead>(
+    mut reader: R,
+    policy: &SafetyPolicy,
+    tolerance: usize,
+    threads: usize,
+) -> Result<Tally, ReadError> {
+    let threads = threads.max(1);
+    let batch_size = BATCH_LINES * threads;
+    let mut batch: Vec<String> = Vec::with_capacity(batch_size);
+    let mut first_line = 1;
+    let mut tally = Tally::default();
+    loop {
+        batch.clear();
+        while batch.len() < batch_size {
+            let mut line = String::new();
+            if reader.read_line(&mut line)? == 0 {
+                break;
+            }
+            batch.push(line);
+        }
+        tally += classify_batch(&batch, first_line, policy, tolerance, threads)?;
+        if batch.len() < batch_size {
+            return Ok(tally);
+        }
+        first_line += batch.len();
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::num_safe_rows;
+    use crate::random;
+    use common::random::Rng;
+
+    const EXAMPLE: &str = "7 6 4 2 1\n1 2 7 8 9\n9 7 6 2 1\n1 3 2 4 5\n8 6 4 4 1\n1 3 6 7 9\n";
+
+    /// Reports of varying length and safety, one per line.
+    fn reports(count: usize) -> String {
+        let mut rng = Rng::new(11);
+        let mut text = String::new();
+        for _ in 0..count {
+            let levels: Vec<String> = random::row(&mut rng, 1..8, 4).iter().map(|level| level.to_string()).collect();
+            text.push_str(&levels.join(" "));
+            text.push('\n');
+        }
+        text
+    }
+
+    #[test]
+    fn test_classify_example() {
+        let policy = SafetyPolicy::default();
+        let tally = classify_stream(EXAMPLE.as_bytes(), &policy, 1, 1).unwrap();
+        assert_eq!(tally, Tally { safe: 2, dampened: 2, failed: 2 });
+        let strict = classify_stream(EXAMPLE.as_bytes(), &policy, 0, 1).unwrap();
+        assert_eq!(strict, Tally { safe: 2, dampened: 0, failed: 4 });
+    }
+
+    #[test]
+    fn test_parallel_matches_sequential() {
+        let policy = SafetyPolicy::default();
+        let text = reports(3 * BATCH_LINES + 17);
+        let rows = crate::parse_input(&text).unwrap();
+        for tolerance in [0, 1, 2] {
+            let sequential = classify_stream(text.as_bytes(), &policy, tolerance, 1).unwrap();
+            assert_eq!(sequential.total(), rows.len());
+            assert_eq!(
+                (sequential.safe + sequential.dampened) as i32,
+                num_safe_rows(&rows, &policy, tolerance),
+            );
+            for threads in [2, 3, 8] {
+                let parallel = classify_stream(text.as_bytes(), &policy, tolerance, threads).unwrap();
+                assert_eq!(parallel, sequential);
+            }
+        }
+    }
+
+    #[test]
+    fn test_empty_batches() {
+        let policy = SafetyPolicy::default();
+        for threads in [1, 2, 4] {
+            assert_eq!(classify_stream("".as_bytes(), &policy, 1, threads).unwrap(), Tally::default());
+        }
+        // Fills the batches exactly, so the last read finds no lines.
+        let text = reports(2 * BATCH_LINES);
+        let sequential = classify_stream(text.as_bytes(), &policy, 1, 1).unwrap();
+        assert_eq!(sequential.total(), 2 * BATCH_LINES);
+        assert_eq!(classify_stream(text.as_bytes(), &policy, 1, 2).unwrap(), sequential);
+    }
+
+    #[test]
+    fn test_stream_error_line() {
+        let policy = SafetyPolicy::default();
+        let mut text = reports(2 * BATCH_LINES);
+        text.push_str("1 2 x\n");
+        text.push_str(&reports(BATCH_LINES));
+        text.push_str("y\n");
+        for threads in [1, 4] {
+            match classify_stream(text.as_bytes(), &policy, 1, threads) {
+                Err(ReadError::Parse(e)) => assert_eq!(e.line, 2 * BATCH_LINES + 1),
+                other => panic!("Expected a parse error, got {:?}", other),
+            }
+        }
+    }
+}
@@ -1,15 +1,15 @@
-use std::{env, fs};
+use common::{parse, Answer, ParseError, ParseErrorKind, Solution};
 use std::vec::Vec;
 
 #[derive(PartialEq, Debug)]
-enum Expr {
+pub enum Expr {
     Do,
     Dont,
     Multiply(Mul),
 }
 
 #[derive(PartialEq, Debug)]
-struct Mul {
+pub struct Mul {
     x: i32,
     y: i32,
 }
@@ -23,17 +23,19 @@ impl Mul {
         self.x * self.y
     }
 
-    fn from_str(s: &str) -> Result<Self, &str> {
-        let parts = s.split(|c: char| c == '(' || c == ',' || c == ')').collect::<Vec<&str>>();
+    fn parse(s: &str, line: usize, column: usize) -> Result<Self, ParseError> {
+        let parts = s.split(['(', ',', ')']).collect::<Vec<&str>>();
         if parts.len() != 4 {
-            return Result::Err("Invalid parts");
+            return Err(ParseError::new(line, column, s, ParseErrorKind::Expected("two arguments")));
         }
         if parts[0] != "mul" {
-            return Result::Err("Invalid function");
+            return Err(ParseError::new(line, column, s, ParseErrorKind::Expected("mul")));
         }
-        let x = parts[1].parse::<i32>().or_else(|_| Result::Err("Invalid x"))?;
-        let y = parts[2].parse::<i32>().or_else(|_| Result::Err("Invalid y"))?;
-        Result::Ok(Self::new(x, y))
+        let x_column = column + parts[0].len() + 1;
+        let y_column = x_column + parts[1].len() + 1;
+        let x = parse::parse_number(line, x_column, parts[1])?;
+        let y = parse::parse_number(line, y_column, parts[2])?;
+        Ok(Self::new(x, y))
     }
 }
 
@@ -43,13 +45,19 @@ impl std::fmt::Display for Mul {
     }
 }
 
-fn parse(input: &str) -> Result<Vec<Expr>, &str> {
+#[allow(clippy::collapsible_match)]
+fn parse(input: &str) -> Result<Vec<Expr>, ParseError> {
     let mut state = String::new();
     let mut exps: Vec<Expr> = Vec::new();
+    let (mut line, mut column) = (1, 1);
+    let mut start = (line, column);
     for char in input.chars() {
+        if state.is_empty() {
+            start = (line, column);
+        }
         match char {
             'm' => {
-                if state == "" {
+                if state.is_empty() {
                     state.push(char);
                 } 
             },
@@ -61,7 +69,7 @@ fn parse(input: &str) -> Result<Vec<Expr>, &str> {
                 }
             },
             'd' => {
-                if state == "" {
+                if state.is_empty() {
                     state.push(char);
                 } 
             },
@@ -124,7 +132,7 @@ fn parse(input: &str) -> Result<Vec<Expr>, &str> {
             ')' => {
                 if state.ends_with(|c: char| c.is_ascii_digit()) {
                     state.push(char);
-                    let mul = Mul::from_str(&state).or_else(|_| Result::Err("Failed to create Mul"))?;
+                    let mul = Mul::parse(&state, start.0, start.1)?;
                     exps.push(Expr::Multiply(mul));
                 } else if state == "do(" {
                     exps.push(Expr::Do);   
@@ -137,11 +145,26 @@ fn parse(input: &str) -> Result<Vec<Expr>, &str> {
                 state.clear();
             }
         }
+        if char == '\n' {
+            line += 1;
+            column = 1;
+        } else {
+            column += 1;
+        }
     }
     Ok(exps)
 }
 
-fn run_expressions(exps: Vec<Expr>) -> i32 {
+fn sum_multiplications(exps: &[Expr]) -> i32 {
+    exps.iter()
+        .map(|exp| match exp {
+            Expr::Multiply(mul) => mul.value(),
+            _ => 0,
+        })
+        .sum()
+}
+
+fn run_expressions(exps: &[Expr]) -> i32 {
     let mut value = 0;
     let mut accumulate = true;
     for exp in exps {
@@ -158,20 +181,23 @@ fn run_expressions(exps: Vec<Expr>) -> i32 {
     value
 }
 
-fn main() {
-    let args: Vec<String> = env::args().collect();
-    if args.len() < 2 {
-        println!("Usage: {} <name>", args[0]);
-        return;
+pub const INPUT: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/input");
+
+pub struct Day3;
+
+impl Solution for Day3 {
+    type Input = Vec<Expr>;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
     }
 
-    match fs::read_to_string(&args[1]) {
-        Ok(content) => {
-            let exps = parse(&content).expect("Failed to parse");
-            let value = run_expressions(exps);
-            println!("Value: {}", value);
-        },
-        Err(e) => eprintln!("Error: {}", e),
+    fn part1(&self, input: &Self::Input) -> Answer {
+        sum_multiplications(input).into()
+    }
+
+    fn part2(&self, input: &Self::Input) -> Answer {
+        run_expressions(input).into()
     }
 }
 
@@ -196,11 +222,20 @@ mod test {
     }
 
     #[test]
-    fn test_mul_from_str() {
-        let exp = Mul::from_str("mul(44,46)").expect("Failed to create from str");
+    fn test_mul_parse() {
+        let exp = Mul::parse("mul(44,46)", 1, 1).expect("Failed to parse mul");
         assert_eq!(exp, Mul::new(44, 46));
     }
 
+    #[test]
+    fn test_parse_number_overflow() {
+        let input = "mul(2,4)\nxxmul(1,99999999999)";
+        assert_eq!(
+            parse(input),
+            Err(ParseError::new(2, 9, "99999999999", ParseErrorKind::InvalidNumber)),
+        );
+    }
+
     #[test]
     fn test_parse_invalid_examples() {
         let input = r#"
@@ -217,15 +252,22 @@ mod test {
     fn test_run_corrupted_example() {
         let input = "xmul(2,4)%&mul[3,7]!@^do_not_mul(5,5)+mul(32,64]then(mul(11,8)mul(8,5))";
         let exps = parse(input).expect("Failed to parse");
-        let value = run_expressions(exps);
+        let value = run_expressions(&exps);
         assert_eq!(value, 161);
     }
 
+    #[test]
+    fn test_sum_multiplications_ignores_do_and_donts() {
+        let input = "xmul(2,4)&mul[3,7]!^don't()_mul(5,5)+mul(32,64](mul(11,8)undo()?mul(8,5))";
+        let exps = parse(input).expect("Failed to parse");
+        assert_eq!(sum_multiplications(&exps), 161);
+    }
+
     #[test]
     fn test_the_do_and_donts() {
         let input = "xmul(2,4)&mul[3,7]!^don't()_mul(5,5)+mul(32,64](mul(11,8)undo()?mul(8,5))";
         let exps = parse(input).expect("Failed to parse");
-        let value = run_expressions(exps);
+        let value = run_expressions(&exps);
         assert_eq!(value, 48);
     }
 }
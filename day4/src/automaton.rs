@@ -0,0 +1,224 @@
+use std::collections::VecDeque;
+
+use grid::{Direction, Line, Point};
+
+use crate::search::Match;
+use crate::Grid;
+
+type Found = (usize, Point, Direction);
+
+#[derive(Debug, Default)]
+struct Node {
+    /// Outgoing edges; nodes have few children so a list beats hashing.
+    next: Vec<(char, usize)>,
+    fail: usize,
+    /// Words ending at this node as `(word index, length, reversed)`.
+    outputs: Vec<(usize, usize, bool)>,
+}
+
+/// Aho–Corasick automaton over a set of words and their reverses, so every
+/// row, column and diagonal of a grid is scanned once, in one direction only.
+pub struct WordSearch<'w> {
+    words: Vec<&'w str>,
+    nodes: Vec<Node>,
+}
+
+impl<'w> WordSearch<'w> {
+    pub fn new(words: &[&'w str]) -> Self {
+        let mut search = WordSearch {
+            words: words.to_vec(),
+            nodes: vec![Node::default()],
+        };
+        for (index, word) in words.iter().enumerate() {
+            let letters: Vec<char> = word.chars().collect();
+            if letters.is_empty() {
+                continue;
+            }
+            search.insert(&letters, (index, letters.len(), false));
+            if letters.len() > 1 {
+                let reversed: Vec<char> = letters.iter().rev().copied().collect();
+                search.insert(&reversed, (index, letters.len(), true));
+            }
+        }
+        search.build_failure_links();
+        search
+    }
+
+    fn insert(&mut self, letters: &[char], output: (usize, usize, bool)) {
+        let mut node = 0;
+        for &letter in letters {
+            node = match self.child(node, letter) {
+                Some(next) => next,
+                None => {
+                    self.nodes.push(Node::default());
+                    let next = self.nodes.len() - 1;
+                    self.nodes[node].next.push((letter, next));
+                    next
+                }
+            };
+        }
+        self.nodes[node].outputs.push(output);
+    }
+
+    fn build_failure_links(&mut self) {
+        let mut queue: VecDeque<usize> = self.nodes[0].next.iter().map(|&(_, n)| n).collect();
+        while let Some(node) = queue.pop_front() {
+            let children = self.nodes[node].next.clone();
+            for (letter, child) in children {
+                let fail = self.transition(self.nodes[node].fail, letter);
+                self.nodes[child].fail = fail;
+                let inherited = self.nodes[fail].outputs.clone();
+                self.nodes[child].outputs.extend(inherited);
+                queue.push_back(child);
+            }
+        }
+    }
+
+    fn child(&self, node: usize, letter: char) -> Option<usize> {
+        self.nodes[node]
+            .next
+            .iter()
+            .find(|&&(c, _)| c == letter)
+            .map(|&(_, next)| next)
+    }
+
+    fn transition(&self, mut node: usize, letter: char) -> usize {
+        loop {
+            if let Some(next) = self.child(node, letter) {
+                return next;
+            }
+            if node == 0 {
+                return 0;
+            }
+            node = self.nodes[node].fail;
+        }
+    }
+
+    /// Feeds one line through the automaton, collecting `(word index, start, direction)`.
+    fn scan(
+        &self,
+        line: Line<'_, char>,
+        direction: Direction,
+        points: &mut Vec<Point>,
+        found: &mut Vec<Found>,
+    ) {
+        points.clear();
+        let mut node = 0;
+        for (point, &letter) in line {
+            points.push(point);
+            node = self.transition(node, letter);
+            for &(word, length, reversed) in &self.nodes[node].outputs {
+                // Single letters read the same every way, report them from rows only.
+                if length == 1 && direction != Direction::East {
+                    continue;
+                }
+                let end = points.len() - 1;
+                if reversed {
+                    found.push((word, points[end], direction.reverse()));
+                } else {
+                    found.push((word, points[end + 1 - length], direction));
+                }
+            }
+        }
+    }
+
+    fn scan_all(&self, grid: &Grid) -> Vec<Found> {
+        let rows = (0..grid.height()).map(|row| grid.line((row, 0), Direction::East));
+        let lines = rows
+            .map(|line| (line, Direction::East))
+            .chain(grid.columns().map(|line| (line, Direction::South)))
+            .chain(grid.diagonals().map(|line| (line, Direction::SouthEast)))
+            .chain(
+                grid.anti_diagonals()
+                    .map(|line| (line, Direction::SouthWest)),
+            );
+
+        let mut points = Vec::new();
+        let mut found = Vec::new();
+        for (line, direction) in lines {
+            self.scan(line, direction, &mut points, &mut found);
+        }
+        found
+    }
+
+    /// Finds every occurrence of each word in all eight directions, in the same
+    /// order as `search::find_words`.
+    pub fn find(&self, grid: &Grid) -> Vec<Match<'w>> {
+        let mut found = self.scan_all(grid);
+        let direction_index = |direction| Direction::ALL.iter().position(|&d| d == direction);
+        found.sort_by_key(|&(word, start, direction)| (start, word, direction_index(direction)));
+        found
+            .into_iter()
+            .map(|(word, start, direction)| Match {
+                word: self.words[word],
+                start,
+                direction,
+            })
+            .collect()
+    }
+
+    /// Number of occurrences of each word, in the order the words were given.
+    pub fn counts(&self, grid: &Grid) -> Vec<(&'w str, usize)> {
+        let mut counts: Vec<(&'w str, usize)> = self.words.iter().map(|&word| (word, 0)).collect();
+        for (word, _, _) in self.scan_all(grid) {
+            counts[word].1 += 1;
+        }
+        counts
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::search::find_words;
+    use common::random::Rng;
+
+    const EXAMPLE: &str = r#"
+        MMMSXXMASM
+        MSAMXMSMSA
+        AMXSXMAAMM
+        MSAMASMSMX
+        XMASAMXAMM
+        XXAMMXXAMA
+        SMSMSASXSS
+        SAXAMASAAA
+        MAMMMXMMMM
+        MXMXAXMASX
+        "#;
+
+    /// Deterministic pseudo-random grid over a small alphabet.
+    fn random_grid(width: usize, height: usize, seed: u64) -> Grid {
+        let mut rng = Rng::new(seed);
+        let cells = (0..width * height).map(|_| b"XMAS"[rng.below(4) as usize] as char).collect();
+        Grid::new(width, height, cells).unwrap()
+    }
+
+    #[test]
+    fn test_counts() {
+        let grid = Grid::parse(EXAMPLE).unwrap();
+        let search = WordSearch::new(&["XMAS", "MAS", "SAM", "Q"]);
+        let counts = search.counts(&grid);
+        assert_eq!(counts[0], ("XMAS", 18));
+        assert_eq!(counts[1].1, counts[2].1);
+        assert_eq!(counts[3], ("Q", 0));
+    }
+
+    #[test]
+    fn test_matches_naive_search() {
+        let words = ["XMAS", "MAS", "SAMX", "AXA", "SS", "A", "XMASX"];
+        for (width, height, seed) in [(10, 10, 1), (17, 5, 2), (3, 23, 3), (1, 9, 4), (30, 30, 5)] {
+            let grid = random_grid(width, height, seed);
+            assert_eq!(
+                WordSearch::new(&words).find(&grid),
+                find_words(&grid, &words)
+            );
+        }
+    }
+
+    #[test]
+    fn test_empty_search() {
+        let grid = Grid::parse(EXAMPLE).unwrap();
+        assert!(WordSearch::new(&[]).find(&grid).is_empty());
+        assert!(WordSearch::new(&[""]).find(&grid).is_empty());
+    }
+}
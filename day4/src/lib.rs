@@ -0,0 +1,234 @@
+use common::{Answer, ParseError, Solution};
+
+pub mod automaton;
+pub mod pattern;
+pub mod render;
+pub mod search;
+
+use grid::View;
+use pattern::{count_pattern, Pattern};
+
+pub type Grid = grid::Grid<char>;
+
+fn parse_grid(input: &str) -> Result<Grid, ParseError> {
+    Grid::parse(input)
+}
+
+fn conv(grid: &Grid, width: usize, height: usize) -> impl Iterator<Item = View<'_, char>> {
+    grid.windows(width, height)
+}
+
+/// The X-MAS cross of part 2 in all its orientations.
+pub fn xmas() -> Pattern {
+    Pattern::parse("M.S/.A./M.S")
+        .expect("X-MAS pattern is valid")
+        .with_symmetries()
+}
+
+pub const INPUT: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/input");
+
+pub struct Day4;
+
+impl Solution for Day4 {
+    type Input = Grid;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, ParseError> {
+        parse_grid(input)
+    }
+
+    fn part1(&self, input: &Self::Input) -> Answer {
+        automaton::WordSearch::new(&["XMAS"]).find(input).len().into()
+    }
+
+    fn part2(&self, input: &Self::Input) -> Answer {
+        count_pattern(input, &xmas()).into()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use common::ParseErrorKind;
+
+    fn grid(rows: Vec<Vec<char>>) -> Grid {
+        Grid::from_rows(rows).expect("Rows must have equal length")
+    }
+
+    #[test]
+    fn test_xmas_grid() {
+        let input = r#"
+        MMMSXXMASM
+        MSAMXMSMSA
+        AMXSXMAAMM
+        MSAMASMSMX
+        XMASAMXAMM
+        XXAMMXXAMA
+        SMSMSASXSS
+        SAXAMASAAA
+        MAMMMXMMMM
+        MXMXAXMASX
+        "#;
+        let grid = parse_grid(input).expect("Failed to parse");
+        assert_eq!(count_pattern(&grid, &xmas()), 9);
+    }
+
+    #[test]
+    fn test_find_xmas_words() {
+        let input = r#"
+        MMMSXXMASM
+        MSAMXMSMSA
+        AMXSXMAAMM
+        MSAMASMSMX
+        XMASAMXAMM
+        XXAMMXXAMA
+        SMSMSASXSS
+        SAXAMASAAA
+        MAMMMXMMMM
+        MXMXAXMASX
+        "#;
+        let grid = parse_grid(input).expect("Failed to parse");
+        assert_eq!(search::find_word(&grid, "XMAS").len(), 18);
+    }
+
+    #[test]
+    fn test_parse_grid() {
+        let input = r#"
+        123
+        456
+        789
+        "#;
+        let parsed = parse_grid(input).expect("Failed to parse");
+        assert_eq!(
+            parsed,
+            grid(vec![
+                vec!['1', '2', '3'],
+                vec!['4', '5', '6'],
+                vec!['7', '8', '9'],
+            ])
+        );
+    }
+
+    #[test]
+    fn test_parse_rectangular_grid() {
+        let input = r#"
+        1234
+        5678
+        "#;
+        let parsed = parse_grid(input).expect("Failed to parse");
+        assert_eq!(parsed.width(), 4);
+        assert_eq!(parsed.height(), 2);
+    }
+
+    #[test]
+    fn test_parse_ragged_grid() {
+        let ragged = "123\n45\n789\n";
+        assert_eq!(
+            parse_grid(ragged),
+            Err(ParseError::new(
+                2,
+                3,
+                "45",
+                ParseErrorKind::RowLength { expected: 3, found: 2 },
+            )),
+        );
+    }
+
+    #[test]
+    fn test_rectangular_xmas_grid() {
+        let input = r#"
+        MMMSXXMASM
+        MSAMXMSMSA
+        AMXSXMAAMM
+        MSAMASMSMX
+        "#;
+        let grid = parse_grid(input).expect("Failed to parse");
+        assert_eq!(search::find_word(&grid, "XMAS").len(), 3);
+        assert_eq!(count_pattern(&grid, &xmas()), 3);
+        assert_eq!(count_pattern(&grid.transpose(), &xmas()), 3);
+    }
+
+    #[test]
+    fn test_xmas_pattern() {
+        let conv = grid(vec![
+            vec!['M', '.', 'S'],
+            vec!['.', 'A', '.'],
+            vec!['M', '.', 'S'],
+        ]);
+        assert_eq!(count_pattern(&conv, &xmas()), 1);
+    }
+
+    #[test]
+    fn test_xmas_pattern_transposed() {
+        let conv = grid(vec![
+            vec!['M', '.', 'M'],
+            vec!['.', 'A', '.'],
+            vec!['S', '.', 'S'],
+        ]);
+        assert_eq!(count_pattern(&conv, &xmas()), 1);
+    }
+
+    #[test]
+    fn test_xmas_pattern_flipped() {
+        let conv = grid(vec![
+            vec!['S', '.', 'M'],
+            vec!['.', 'A', '.'],
+            vec!['S', '.', 'M'],
+        ]);
+        assert_eq!(count_pattern(&conv, &xmas()), 1);
+    }
+
+    #[test]
+    fn test_xmas_pattern_transposed_flipped() {
+        let conv = grid(vec![
+            vec!['S', '.', 'S'],
+            vec!['.', 'A', '.'],
+            vec!['M', '.', 'M'],
+        ]);
+        assert_eq!(count_pattern(&conv, &xmas()), 1);
+    }
+
+
+    #[test]
+    fn test_conv_rectangular() {
+        let input = grid(vec![
+            vec!['0', '1', '2'],
+            vec!['3', '4', '5'],
+        ]);
+        assert_eq!(conv(&input, 2, 2).count(), 2);
+        assert_eq!(conv(&input.transpose(), 2, 2).count(), 2);
+        assert_eq!(conv(&input, 3, 3).count(), 0);
+    }
+
+    #[test]
+    fn test_conv() {
+        let input = grid(vec![
+            vec!['0', '1', '2'],
+            vec!['3', '4', '5'],
+            vec!['6', '8', '9'],
+        ]);
+        assert_eq!(
+            conv(&input, 2, 2).map(|view| view.to_grid()).collect::<Vec<_>>(),
+            vec![
+                vec![
+                    vec!['0', '1'],
+                    vec!['3', '4'],
+                ],
+                vec![
+                    vec!['1', '2'],
+                    vec!['4', '5'],
+                ],
+                vec![
+                    vec!['3', '4'],
+                    vec!['6', '8'],
+                ],
+                vec![
+                    vec!['4', '5'],
+                    vec!['8', '9'],
+                ],
+            ]
+            .into_iter()
+            .map(grid)
+            .collect::<Vec<_>>(),
+        );
+    }
+}
@@ -0,0 +1,169 @@
+use common::{ParseError, ParseErrorKind};
+use grid::{Point, View};
+
+use crate::{conv, Grid};
+
+/// A 2D template where `None` cells match anything.
+pub type Template = grid::Grid<Option<char>>;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Pattern {
+    variants: Vec<Template>,
+}
+
+fn matches(template: &Template, window: &View<char>) -> bool {
+    template
+        .iter()
+        .all(|(point, cell)| cell.is_none_or(|expected| window[point] == expected))
+}
+
+impl Pattern {
+    /// Parses rows separated by `/`, with `.` as a wildcard, e.g. `M.S/.A./M.S`.
+    /// Empty patterns and empty rows are rejected.
+    pub fn parse(text: &str) -> Result<Pattern, ParseError> {
+        let mut column = 1;
+        for row in text.split('/') {
+            if row.trim().is_empty() {
+                return Err(ParseError::new(1, column, text, ParseErrorKind::MissingValue("pattern row")));
+            }
+            column += row.chars().count() + 1;
+        }
+        let grid = Grid::parse(&text.replace('/', "\n"))?;
+        let template = grid.map(|&c| if c == '.' { None } else { Some(c) });
+        Ok(Pattern { variants: vec![template] })
+    }
+
+    /// Adds every rotation and reflection of the pattern, dropping duplicates
+    /// so symmetric patterns are not counted twice.
+    pub fn with_symmetries(self) -> Pattern {
+        let mut variants: Vec<Template> = Vec::new();
+        for template in self.variants {
+            let mut rotated = template;
+            for _ in 0..4 {
+                for variant in [rotated.flip_horizontal(), rotated.clone()] {
+                    if !variants.contains(&variant) {
+                        variants.push(variant);
+                    }
+                }
+                rotated = rotated.rotate_clockwise();
+            }
+        }
+        Pattern { variants }
+    }
+
+    pub fn variants(&self) -> &[Template] {
+        &self.variants
+    }
+}
+
+/// A window of the grid matching one variant of a pattern.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PatternMatch<'p> {
+    pub origin: Point,
+    pub template: &'p Template,
+}
+
+impl PatternMatch<'_> {
+    /// The cells the template pins down, skipping wildcards.
+    pub fn points(&self) -> impl Iterator<Item = Point> + '_ {
+        let (row, col) = self.origin;
+        self.template
+            .iter()
+            .filter(|(_, cell)| cell.is_some())
+            .map(move |((dr, dc), _)| (row + dr, col + dc))
+    }
+}
+
+/// Finds the windows of `grid` matching any variant of `pattern`, grouped by variant.
+pub fn find_pattern<'p>(grid: &Grid, pattern: &'p Pattern) -> Vec<PatternMatch<'p>> {
+    pattern
+        .variants
+        .iter()
+        .flat_map(|template| {
+            conv(grid, template.width(), template.height())
+                .filter(|window| matches(template, window))
+                .map(move |window| PatternMatch { origin: window.origin(), template })
+        })
+        .collect()
+}
+
+/// Counts the windows of `grid` matching any variant of `pattern`.
+pub fn count_pattern(grid: &Grid, pattern: &Pattern) -> usize {
+    pattern
+        .variants
+        .iter()
+        .map(|template| {
+            conv(grid, template.width(), template.height())
+                .filter(|window| matches(template, window))
+                .count()
+        })
+        .sum()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let pattern = Pattern::parse("M.S/.A./M.S").expect("Failed to parse");
+        let template = &pattern.variants()[0];
+        assert_eq!(template.width(), 3);
+        assert_eq!(template.height(), 3);
+        assert_eq!(template[(0, 0)], Some('M'));
+        assert_eq!(template[(0, 1)], None);
+        assert_eq!(template[(1, 1)], Some('A'));
+        assert!(Pattern::parse("M.S/.A/M.S").is_err());
+    }
+
+    #[test]
+    fn test_parse_empty() {
+        let missing = |column, text| ParseError::new(1, column, text, ParseErrorKind::MissingValue("pattern row"));
+        assert_eq!(Pattern::parse(""), Err(missing(1, "")));
+        assert_eq!(Pattern::parse("  "), Err(missing(1, "  ")));
+        assert_eq!(Pattern::parse("M.S//M.S"), Err(missing(5, "M.S//M.S")));
+        assert_eq!(Pattern::parse("M.S/"), Err(missing(5, "M.S/")));
+    }
+
+    #[test]
+    fn test_symmetries() {
+        let xmas = Pattern::parse("M.S/.A./M.S").unwrap().with_symmetries();
+        assert_eq!(xmas.variants().len(), 4);
+
+        let asymmetric = Pattern::parse("AB./...").unwrap().with_symmetries();
+        assert_eq!(asymmetric.variants().len(), 8);
+
+        let symmetric = Pattern::parse("A.A/.A./A.A").unwrap().with_symmetries();
+        assert_eq!(symmetric.variants().len(), 1);
+    }
+
+    #[test]
+    fn test_count_pattern() {
+        let grid = Grid::parse("MXS\nXAX\nMXS\n").unwrap();
+        let single = Pattern::parse("M.S/.A./M.S").unwrap();
+        assert_eq!(count_pattern(&grid, &single), 1);
+        assert_eq!(count_pattern(&grid.rotate_clockwise(), &single), 0);
+        assert_eq!(count_pattern(&grid.rotate_clockwise(), &single.with_symmetries()), 1);
+    }
+
+    #[test]
+    fn test_find_pattern() {
+        let grid = Grid::parse("MXSX\nXAXA\nMXSX\n").unwrap();
+        let xmas = Pattern::parse("M.S/.A./M.S").unwrap().with_symmetries();
+        let found = find_pattern(&grid, &xmas);
+        assert_eq!(found.len(), count_pattern(&grid, &xmas));
+        assert_eq!(found[0].origin, (0, 0));
+        assert_eq!(
+            found[0].points().collect::<Vec<_>>(),
+            vec![(0, 0), (0, 2), (1, 1), (2, 0), (2, 2)],
+        );
+    }
+
+    #[test]
+    fn test_count_rectangular_pattern() {
+        let grid = Grid::parse("ABAB\nBABA\n").unwrap();
+        let pattern = Pattern::parse("AB").unwrap().with_symmetries();
+        assert_eq!(pattern.variants().len(), 4);
+        assert_eq!(count_pattern(&grid, &pattern), 10);
+    }
+}
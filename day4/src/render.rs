@@ -0,0 +1,217 @@
+use std::collections::HashMap;
+
+use grid::Point;
+
+use crate::pattern::PatternMatch;
+use crate::search::Match;
+use crate::Grid;
+
+/// Side of one grid cell in the SVG output, in pixels.
+const CELL: usize = 24;
+
+/// Shape drawn around a match in the SVG output.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Outline {
+    /// A capsule from the first to the last letter of a word.
+    Segment(Point, Point),
+    /// A box around a pattern window.
+    Window { origin: Point, width: usize, height: usize },
+}
+
+/// The cells covered by one match and the outline drawn around it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Highlight {
+    pub cells: Vec<Point>,
+    pub outline: Outline,
+}
+
+impl From<&Match<'_>> for Highlight {
+    fn from(found: &Match) -> Self {
+        let cells: Vec<Point> = found.points().collect();
+        let first = cells.first().copied().unwrap_or(found.start);
+        let last = cells.last().copied().unwrap_or(found.start);
+        Highlight { cells, outline: Outline::Segment(first, last) }
+    }
+}
+
+impl From<&PatternMatch<'_>> for Highlight {
+    fn from(found: &PatternMatch) -> Self {
+        Highlight {
+            cells: found.points().collect(),
+            outline: Outline::Window {
+                origin: found.origin,
+                width: found.template.width(),
+                height: found.template.height(),
+            },
+        }
+    }
+}
+
+/// Number of highlights covering each cell.
+fn coverage(highlights: &[Highlight]) -> HashMap<Point, usize> {
+    let mut coverage = HashMap::new();
+    for highlight in highlights {
+        for &point in &highlight.cells {
+            *coverage.entry(point).or_insert(0) += 1;
+        }
+    }
+    coverage
+}
+
+/// Prints the grid with every cell outside a match replaced by `.`, like the
+/// puzzle statement. With `colour`, matched letters are green, or yellow when
+/// several matches share them.
+pub fn text(grid: &Grid, highlights: &[Highlight], colour: bool) -> String {
+    let coverage = coverage(highlights);
+    let mut out = String::new();
+    for (row, cells) in grid.rows().enumerate() {
+        for (col, &cell) in cells.iter().enumerate() {
+            match (coverage.get(&(row, col)), colour) {
+                (None, _) => out.push('.'),
+                (Some(_), false) => out.push(cell),
+                (Some(1), true) => out.push_str(&format!("\x1b[1;32m{}\x1b[0m", cell)),
+                (Some(_), true) => out.push_str(&format!("\x1b[1;33m{}\x1b[0m", cell)),
+            }
+        }
+        out.push('\n');
+    }
+    out
+}
+
+fn centre((row, col): Point) -> (f64, f64) {
+    let half = CELL as f64 / 2.0;
+    (col as f64 * CELL as f64 + half, row as f64 * CELL as f64 + half)
+}
+
+fn escape_xml(c: char) -> String {
+    match c {
+        '<' => "&lt;".to_string(),
+        '>' => "&gt;".to_string(),
+        '&' => "&amp;".to_string(),
+        c => c.to_string(),
+    }
+}
+
+fn outline_svg(outline: Outline, colour: &str) -> String {
+    match outline {
+        Outline::Segment(first, last) => {
+            let (x1, y1) = centre(first);
+            let (x2, y2) = centre(last);
+            let (dx, dy) = (x2 - x1, y2 - y1);
+            let radius = CELL as f64 * 0.45;
+            format!(
+                r#"<rect x="{:.1}" y="{:.1}" width="{:.2}" height="{:.1}" rx="{:.1}" transform="translate({:.1} {:.1}) rotate({:.0})" fill="none" stroke="{}" stroke-width="2"/>"#,
+                -radius,
+                -radius,
+                dx.hypot(dy) + 2.0 * radius,
+                2.0 * radius,
+                radius,
+                x1,
+                y1,
+                dy.atan2(dx).to_degrees(),
+                colour,
+            )
+        }
+        Outline::Window { origin: (row, col), width, height } => format!(
+            r#"<rect x="{}" y="{}" width="{}" height="{}" rx="4" fill="none" stroke="{}" stroke-width="2"/>"#,
+            col * CELL + 2,
+            row * CELL + 2,
+            width * CELL - 4,
+            height * CELL - 4,
+            colour,
+        ),
+    }
+}
+
+/// Renders the grid as an SVG image with each match outlined in its own colour
+/// and letters outside every match greyed out.
+pub fn svg(grid: &Grid, highlights: &[Highlight]) -> String {
+    let coverage = coverage(highlights);
+    let (width, height) = (grid.width() * CELL, grid.height() * CELL);
+    let mut out = format!(
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{0}" height="{1}" viewBox="0 0 {0} {1}" font-family="monospace" font-size="16" text-anchor="middle" dominant-baseline="central">"#,
+        width, height,
+    );
+    out.push('\n');
+    out.push_str(&format!(r#"<rect width="{}" height="{}" fill="white"/>"#, width, height));
+    out.push('\n');
+    for (point, &cell) in grid.iter() {
+        let (x, y) = centre(point);
+        let fill = if coverage.contains_key(&point) { "black" } else { "#bbb" };
+        out.push_str(&format!(
+            r#"<text x="{:.0}" y="{:.0}" fill="{}">{}</text>"#,
+            x,
+            y,
+            fill,
+            escape_xml(cell),
+        ));
+        out.push('\n');
+    }
+    for (index, highlight) in highlights.iter().enumerate() {
+        // Golden-angle hue steps keep neighbouring matches apart.
+        let colour = format!("hsl({}, 70%, 45%)", index * 137 % 360);
+        out.push_str(&outline_svg(highlight.outline, &colour));
+        out.push('\n');
+    }
+    out.push_str("</svg>\n");
+    out
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::pattern::{find_pattern, Pattern};
+    use crate::search::find_word;
+
+    fn grid() -> Grid {
+        Grid::parse(
+            r#"
+            XMASX
+            SM.MM
+            A.AAA
+            XS.SS
+            "#,
+        )
+        .expect("Failed to parse")
+    }
+
+    #[test]
+    fn test_text() {
+        let grid = grid();
+        let highlights: Vec<Highlight> = find_word(&grid, "XMAS").iter().map(Highlight::from).collect();
+        assert_eq!(highlights.len(), 4);
+        assert_eq!(text(&grid, &highlights, false), "XMASX\n.M.MM\n..A.A\n.S.SS\n");
+    }
+
+    #[test]
+    fn test_text_colour() {
+        let grid = Grid::parse("M.S.M\n.A.A.\nM.S.M\n").unwrap();
+        let xmas = Pattern::parse("M.S/.A./M.S").unwrap().with_symmetries();
+        let highlights: Vec<Highlight> = find_pattern(&grid, &xmas).iter().map(Highlight::from).collect();
+        assert_eq!(highlights.len(), 2);
+        let rendered = text(&grid, &highlights, true);
+        assert_eq!(rendered.matches("\x1b[1;32m").count(), 6);
+        assert_eq!(rendered.matches("\x1b[1;33m").count(), 2);
+        assert!(rendered.starts_with("\x1b[1;32mM\x1b[0m."));
+    }
+
+    #[test]
+    fn test_svg() {
+        let grid = grid();
+        let highlights: Vec<Highlight> = find_word(&grid, "XMAS").iter().map(Highlight::from).collect();
+        let image = svg(&grid, &highlights);
+        assert!(image.starts_with("<svg "));
+        assert!(image.ends_with("</svg>\n"));
+        assert_eq!(image.matches("<text ").count(), 20);
+        assert_eq!(image.matches(r#"fill="black""#).count(), 13);
+        for angle in [0, 45, 90, 135] {
+            assert_eq!(image.matches(&format!("rotate({})", angle)).count(), 1);
+        }
+    }
+
+    #[test]
+    fn test_highlight_outlines() {
+        let found = Match { word: "XMAS", start: (3, 3), direction: grid::Direction::NorthWest };
+        assert_eq!(Highlight::from(&found).outline, Outline::Segment((3, 3), (0, 0)));
+    }
+}
@@ -0,0 +1,114 @@
+use grid::{Direction, Point};
+
+use crate::Grid;
+
+/// One occurrence of a word, read from `start` towards `direction`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Match<'w> {
+    pub word: &'w str,
+    pub start: Point,
+    pub direction: Direction,
+}
+
+impl Match<'_> {
+    /// The cells covered by the match, in reading order.
+    pub fn points(&self) -> impl Iterator<Item = Point> + '_ {
+        let (dr, dc) = self.direction.offset();
+        let (row, col) = self.start;
+        (0..self.word.chars().count() as isize).map(move |i| {
+            (
+                row.wrapping_add_signed(dr * i),
+                col.wrapping_add_signed(dc * i),
+            )
+        })
+    }
+}
+
+fn word_at(grid: &Grid, word: &[char], start: Point, direction: Direction) -> bool {
+    grid.line(start, direction)
+        .map(|(_, &cell)| cell)
+        .take(word.len())
+        .eq(word.iter().copied())
+}
+
+/// Finds every occurrence of each word in all eight directions, ordered by
+/// start position. Single letter words are reported once per cell.
+pub fn find_words<'w>(grid: &Grid, words: &[&'w str]) -> Vec<Match<'w>> {
+    let words: Vec<(&str, Vec<char>)> = words
+        .iter()
+        .filter(|word| !word.is_empty())
+        .map(|&word| (word, word.chars().collect()))
+        .collect();
+    let mut matches = Vec::new();
+    for start in grid.points() {
+        for (word, letters) in &words {
+            let directions: &[Direction] = if letters.len() == 1 {
+                &[Direction::East]
+            } else {
+                &Direction::ALL
+            };
+            for &direction in directions {
+                if word_at(grid, letters, start, direction) {
+                    matches.push(Match { word, start, direction });
+                }
+            }
+        }
+    }
+    matches
+}
+
+pub fn find_word<'w>(grid: &Grid, word: &'w str) -> Vec<Match<'w>> {
+    find_words(grid, &[word])
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn grid() -> Grid {
+        Grid::parse(
+            r#"
+            ..X...
+            .SAMX.
+            .A..A.
+            XMAS.S
+            .X....
+            "#,
+        )
+        .expect("Failed to parse")
+    }
+
+    #[test]
+    fn test_find_word() {
+        let matches = find_word(&grid(), "XMAS");
+        assert_eq!(
+            matches,
+            vec![
+                Match { word: "XMAS", start: (0, 2), direction: Direction::SouthEast },
+                Match { word: "XMAS", start: (1, 4), direction: Direction::West },
+                Match { word: "XMAS", start: (3, 0), direction: Direction::East },
+                Match { word: "XMAS", start: (4, 1), direction: Direction::North },
+            ],
+        );
+    }
+
+    #[test]
+    fn test_match_points() {
+        let found = Match { word: "XMAS", start: (1, 4), direction: Direction::West };
+        assert_eq!(found.points().collect::<Vec<_>>(), vec![(1, 4), (1, 3), (1, 2), (1, 1)]);
+    }
+
+    #[test]
+    fn test_find_words() {
+        let matches = find_words(&grid(), &["SAMX", "AS", "Q", ""]);
+        let count = |word| matches.iter().filter(|m| m.word == word).count();
+        assert_eq!(count("SAMX"), 4);
+        assert_eq!(count("AS"), 5);
+        assert_eq!(count("Q"), 0);
+    }
+
+    #[test]
+    fn test_find_single_letter() {
+        assert_eq!(find_word(&grid(), "X").len(), 4);
+    }
+}
@@ -1,9 +1,9 @@
-use std::{env, fs, process};
+use common::{parse, Answer, ParseError, ParseErrorKind, Solution};
 
-type PageOrdering = Vec<i32>;
+pub type PageOrdering = Vec<i32>;
 
 #[derive(Debug, PartialEq, Clone)]
-struct Rule {
+pub struct Rule {
     before: i32,
     after: i32,
 }
@@ -13,51 +13,51 @@ impl Rule {
         Rule { before, after }
     }
 
-    fn parse(line: &str) -> Result<Rule, &str> {
-        let mut parts = line.trim().split('|');
-        let before = parts.next().map_or(Err("Missing before"), |part| {
-            part.trim().parse().map_err(|_| "Invalid before")
-        })?;
-        let after = parts.next().map_or(Err("Missing after"), |part| {
-            part.trim().parse().map_err(|_| "Invalid after")
-        })?;
+    fn parse(line: &str, line_number: usize) -> Result<Rule, ParseError> {
+        let missing = |what| {
+            let column = line.trim_end().chars().count() + 1;
+            ParseError::new(line_number, column, line.trim(), ParseErrorKind::MissingValue(what))
+        };
+        let mut parts = parse::split_columns(line, '|');
+        let (before_column, before) = parts.next().ok_or_else(|| missing("before"))?;
+        let (after_column, after) = parts.next().ok_or_else(|| missing("after"))?;
+        let before = parse::parse_number(line_number, before_column, before)?;
+        let after = parse::parse_number(line_number, after_column, after)?;
         Ok(Rule::new(before, after))
     }
 
     fn applicable(&self, ordering: &[i32]) -> bool {
-        let includes_before = ordering.iter().any(|&page| page == self.before);
-        let includes_after = ordering.iter().any(|&page| page == self.after);
+        let includes_before = ordering.contains(&self.before);
+        let includes_after = ordering.contains(&self.after);
         includes_before && includes_after
     }
 }
 
-fn parse_input(input: &str) -> Result<(Vec<Rule>, Vec<PageOrdering>), &str> {
+fn parse_input(input: &str) -> Result<(Vec<Rule>, Vec<PageOrdering>), ParseError> {
     let mut rules: Vec<Rule> = Vec::new();
     let mut page_orderings: Vec<PageOrdering> = Vec::new();
-    let mut lines = input.lines();
+    let mut lines = input.lines().enumerate().map(|(index, line)| (index + 1, line));
 
-    while let Some(line) = lines.next() {
+    for (line_number, line) in lines.by_ref() {
         if !line.is_empty() {
-            rules.push(Rule::parse(line)?);
+            rules.push(Rule::parse(line, line_number)?);
             break;
         }
     }
 
-    while let Some(line) = lines.next() {
+    for (line_number, line) in lines.by_ref() {
         if line.is_empty() {
             break;
         }
 
-        rules.push(Rule::parse(line)?);
+        rules.push(Rule::parse(line, line_number)?);
     }
 
-    while let Some(line) = lines.next() {
-        let trimmed = line.trim();
-        if !trimmed.is_empty() {
-            let ordering = trimmed
-                .split(',')
-                .map(|part| part.parse().map_err(|_| "Invalid page number"))
-                .collect::<Result<Vec<i32>, &str>>()?;
+    for (line_number, line) in lines {
+        if !line.trim().is_empty() {
+            let ordering = parse::split_columns(line, ',')
+                .map(|(column, part)| parse::parse_number(line_number, column, part))
+                .collect::<Result<Vec<i32>, ParseError>>()?;
             page_orderings.push(ordering);
         }
     }
@@ -112,7 +112,7 @@ fn sum_of_middle_valid_numbers(page_orderings: &[PageOrdering], rules: &[Rule])
     page_orderings
         .iter()
         .filter(|ordering| in_order(ordering, rules))
-        .map(|ordering| middle(&ordering))
+        .map(|ordering| middle(ordering))
         .sum()
 }
 
@@ -125,39 +125,30 @@ fn sum_of_middle_corrected_numbers(page_orderings: &[PageOrdering], rules: &[Rul
         .sum()
 }
 
-fn main() {
-    let args: Vec<String> = env::args().collect();
+pub const INPUT: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/input");
+
+pub struct Day5;
+
+impl Solution for Day5 {
+    type Input = (Vec<Rule>, Vec<PageOrdering>);
 
-    if args.len() < 2 {
-        eprintln!("Usage: {} <input_file>", args[0]);
-        process::exit(1);
+    fn parse(&self, input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
     }
 
-    match fs::read_to_string(&args[1]) {
-        Ok(input) => match parse_input(&input) {
-            Ok((rules, page_orderings)) => {
-                println!(
-                    "Sum middle valid orderings {}",
-                    sum_of_middle_valid_numbers(&page_orderings, &rules)
-                );
-                println!(
-                    "Sum middle corrected orderings {}",
-                    sum_of_middle_corrected_numbers(&page_orderings, &rules)
-                );
-            }
-            Err(e) => {
-                eprintln!("Error parsing input: {}", e);
-                process::exit(1);
-            }
-        },
-        Err(e) => {
-            eprintln!("Error reading file: {}", e);
-            process::exit(1);
-        }
+    fn part1(&self, input: &Self::Input) -> Answer {
+        let (rules, page_orderings) = input;
+        sum_of_middle_valid_numbers(page_orderings, rules).into()
+    }
+
+    fn part2(&self, input: &Self::Input) -> Answer {
+        let (rules, page_orderings) = input;
+        sum_of_middle_corrected_numbers(page_orderings, rules).into()
     }
 }
 
 #[cfg(test)]
+#[allow(clippy::bool_assert_comparison, clippy::useless_vec, clippy::needless_borrow)]
 mod test {
     use super::*;
 
@@ -260,11 +251,32 @@ mod test {
 
     #[test]
     fn test_parse_rule() {
-        let rule = Rule::parse("47|53").expect("Failed to parse rule");
+        let rule = Rule::parse("47|53", 1).expect("Failed to parse rule");
         assert_eq!(rule.before, 47);
         assert_eq!(rule.after, 53);
     }
 
+    #[test]
+    fn test_parse_invalid_rule() {
+        assert_eq!(
+            Rule::parse("47|5x", 3),
+            Err(ParseError::new(3, 4, "5x", ParseErrorKind::InvalidNumber)),
+        );
+        assert_eq!(
+            Rule::parse("47", 4),
+            Err(ParseError::new(4, 3, "47", ParseErrorKind::MissingValue("after"))),
+        );
+    }
+
+    #[test]
+    fn test_parse_invalid_page_number() {
+        let input = "47|53\n\n75,4x,61\n";
+        assert_eq!(
+            parse_input(input),
+            Err(ParseError::new(3, 4, "4x", ParseErrorKind::InvalidNumber)),
+        );
+    }
+
     #[test]
     fn test_applicable_rule() {
         let ordering = vec![75, 47, 61, 53, 29];
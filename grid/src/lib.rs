@@ -0,0 +1,442 @@
+use std::fmt;
+use std::ops::{Index, IndexMut};
+
+use common::{ParseError, ParseErrorKind};
+
+mod direction;
+mod view;
+
+pub use direction::Direction;
+pub use view::View;
+
+/// A cell position as `(row, column)`.
+pub type Point = (usize, usize);
+
+/// A rectangular grid stored row by row in a single `Vec`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// Builds a grid from row-major `cells`, returning `None` if the length is not `width * height`.
+    pub fn new(width: usize, height: usize, cells: Vec<T>) -> Option<Self> {
+        if cells.len() != width * height {
+            return None;
+        }
+        Some(Self { width, height, cells })
+    }
+
+    /// Builds a grid from rows, returning the index of the first row whose length differs from the first.
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Result<Self, usize> {
+        let width = rows.first().map_or(0, |row| row.len());
+        let height = rows.len();
+        if let Some(index) = rows.iter().position(|row| row.len() != width) {
+            return Err(index);
+        }
+        let cells = rows.into_iter().flatten().collect();
+        Ok(Self { width, height, cells })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, (row, col): Point) -> bool {
+        row < self.height && col < self.width
+    }
+
+    pub fn get(&self, point: Point) -> Option<&T> {
+        if self.contains(point) {
+            Some(&self.cells[point.0 * self.width + point.1])
+        } else {
+            None
+        }
+    }
+
+    pub fn get_mut(&mut self, point: Point) -> Option<&mut T> {
+        if self.contains(point) {
+            Some(&mut self.cells[point.0 * self.width + point.1])
+        } else {
+            None
+        }
+    }
+
+    /// The point `steps` moves from `point` in `direction`, if it is inside the grid.
+    pub fn step(&self, (row, col): Point, direction: Direction, steps: usize) -> Option<Point> {
+        let (dr, dc) = direction.offset();
+        let row = row.checked_add_signed(dr * steps as isize)?;
+        let col = col.checked_add_signed(dc * steps as isize)?;
+        self.contains((row, col)).then_some((row, col))
+    }
+
+    pub fn neighbours4(&self, point: Point) -> impl Iterator<Item = Point> + '_ {
+        Direction::ORTHOGONAL
+            .into_iter()
+            .filter_map(move |direction| self.step(point, direction, 1))
+    }
+
+    pub fn neighbours8(&self, point: Point) -> impl Iterator<Item = Point> + '_ {
+        Direction::ALL
+            .into_iter()
+            .filter_map(move |direction| self.step(point, direction, 1))
+    }
+
+    pub fn points(&self) -> impl Iterator<Item = Point> {
+        let width = self.width;
+        (0..self.height).flat_map(move |row| (0..width).map(move |col| (row, col)))
+    }
+
+    /// Iterates over every cell together with its position, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = (Point, &T)> {
+        self.points().zip(self.cells.iter())
+    }
+
+    pub fn row(&self, row: usize) -> &[T] {
+        &self.cells[row * self.width..(row + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        (0..self.height).map(move |row| self.row(row))
+    }
+
+    pub fn column(&self, col: usize) -> Line<'_, T> {
+        self.line((0, col), Direction::South)
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = Line<'_, T>> {
+        (0..self.width).map(move |col| self.column(col))
+    }
+
+    /// Walks from `start` in `direction` until leaving the grid.
+    pub fn line(&self, start: Point, direction: Direction) -> Line<'_, T> {
+        Line {
+            grid: self,
+            next: self.contains(start).then_some(start),
+            direction,
+        }
+    }
+
+    /// Every diagonal running down and to the right, starting from the bottom-left corner.
+    pub fn diagonals(&self) -> impl Iterator<Item = Line<'_, T>> {
+        let from_left = (0..self.height).rev().map(|row| (row, 0));
+        let from_top = (1..self.width).map(|col| (0, col));
+        from_left
+            .chain(from_top)
+            .map(move |start| self.line(start, Direction::SouthEast))
+    }
+
+    /// Every diagonal running down and to the left, starting from the top-left corner.
+    pub fn anti_diagonals(&self) -> impl Iterator<Item = Line<'_, T>> {
+        let last_col = self.width.saturating_sub(1);
+        let from_top = (0..self.width).map(|col| (0, col));
+        let from_right = (1..self.height).map(move |row| (row, last_col));
+        from_top
+            .chain(from_right)
+            .map(move |start| self.line(start, Direction::SouthWest))
+    }
+
+    pub fn map<U, F: FnMut(&T) -> U>(&self, f: F) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+
+    fn remap<F: Fn(Point) -> Point>(&self, width: usize, height: usize, source: F) -> Grid<T>
+    where
+        T: Clone,
+    {
+        let cells = (0..height)
+            .flat_map(|row| (0..width).map(move |col| (row, col)))
+            .map(|point| self[source(point)].clone())
+            .collect();
+        Grid { width, height, cells }
+    }
+
+    pub fn transpose(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        self.remap(self.height, self.width, |(row, col)| (col, row))
+    }
+
+    pub fn rotate_clockwise(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        let height = self.height;
+        self.remap(self.height, self.width, |(row, col)| (height - 1 - col, row))
+    }
+
+    pub fn rotate_counterclockwise(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        let width = self.width;
+        self.remap(self.height, self.width, |(row, col)| (col, width - 1 - row))
+    }
+
+    /// Mirrors the grid left to right.
+    pub fn flip_horizontal(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        let width = self.width;
+        self.remap(self.width, self.height, |(row, col)| (row, width - 1 - col))
+    }
+
+    /// Mirrors the grid top to bottom.
+    pub fn flip_vertical(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        let height = self.height;
+        self.remap(self.width, self.height, |(row, col)| (height - 1 - row, col))
+    }
+
+    /// Borrows the `height` x `width` block whose top-left corner is at `origin`.
+    pub fn view(&self, origin: Point, width: usize, height: usize) -> Option<View<'_, T>> {
+        View::new(self, origin, width, height)
+    }
+
+    /// Every `height` x `width` window of the grid, row by row, without copying cells.
+    pub fn windows(&self, width: usize, height: usize) -> impl Iterator<Item = View<'_, T>> {
+        let rows = (self.height + 1).saturating_sub(height);
+        let cols = (self.width + 1).saturating_sub(width);
+        (0..rows)
+            .flat_map(move |row| (0..cols).map(move |col| (row, col)))
+            .filter_map(move |origin| self.view(origin, width, height))
+    }
+
+    /// Copies the `height` x `width` block whose top-left corner is at `origin`.
+    pub fn subgrid(&self, origin: Point, width: usize, height: usize) -> Option<Grid<T>>
+    where
+        T: Clone,
+    {
+        self.view(origin, width, height).map(|view| view.to_grid())
+    }
+}
+
+impl Grid<char> {
+    /// Parses one row per non-blank line, ignoring surrounding whitespace.
+    pub fn parse(input: &str) -> Result<Self, ParseError> {
+        let mut width = None;
+        let mut height = 0;
+        let mut cells = Vec::new();
+        for (index, line) in input.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+            let row_width = line.chars().count();
+            let expected = *width.get_or_insert(row_width);
+            if row_width != expected {
+                let kind = ParseErrorKind::RowLength { expected, found: row_width };
+                return Err(ParseError::new(index + 1, row_width.min(expected) + 1, line, kind));
+            }
+            cells.extend(line.chars());
+            height += 1;
+        }
+        Ok(Self {
+            width: width.unwrap_or(0),
+            height,
+            cells,
+        })
+    }
+}
+
+impl<T> Index<Point> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, point: Point) -> &T {
+        self.get(point).expect("Point outside of grid")
+    }
+}
+
+impl<T> IndexMut<Point> for Grid<T> {
+    fn index_mut(&mut self, point: Point) -> &mut T {
+        self.get_mut(point).expect("Point outside of grid")
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for row in self.rows() {
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+/// Cells visited by walking through the grid in a straight line.
+pub struct Line<'a, T> {
+    grid: &'a Grid<T>,
+    next: Option<Point>,
+    direction: Direction,
+}
+
+impl<'a, T> Iterator for Line<'a, T> {
+    type Item = (Point, &'a T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let point = self.next?;
+        self.next = self.grid.step(point, self.direction, 1);
+        Some((point, &self.grid[point]))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn grid() -> Grid<char> {
+        Grid::parse(
+            r#"
+            abc
+            def
+            "#,
+        )
+        .expect("Failed to parse")
+    }
+
+    fn values<'a>(line: impl Iterator<Item = (Point, &'a char)>) -> String {
+        line.map(|(_, &c)| c).collect()
+    }
+
+    #[test]
+    fn test_parse() {
+        let grid = grid();
+        assert_eq!(grid.width(), 3);
+        assert_eq!(grid.height(), 2);
+        assert_eq!(grid[(1, 2)], 'f');
+        assert_eq!(grid.get((2, 0)), None);
+        assert_eq!(grid.get((0, 3)), None);
+    }
+
+    #[test]
+    fn test_parse_ragged_rows() {
+        assert_eq!(
+            Grid::parse("abc\nde\n"),
+            Err(ParseError::new(
+                2,
+                3,
+                "de",
+                ParseErrorKind::RowLength { expected: 3, found: 2 },
+            )),
+        );
+    }
+
+    #[test]
+    fn test_from_rows() {
+        let grid = Grid::from_rows(vec![vec![1, 2], vec![3, 4]]).expect("Failed to build");
+        assert_eq!(grid, Grid::new(2, 2, vec![1, 2, 3, 4]).unwrap());
+        assert_eq!(Grid::from_rows(vec![vec![1, 2], vec![3]]), Err(1));
+        assert_eq!(Grid::new(2, 2, vec![1, 2, 3]), None);
+    }
+
+    #[test]
+    fn test_neighbours() {
+        let grid = grid();
+        assert_eq!(
+            grid.neighbours4((0, 0)).collect::<Vec<_>>(),
+            vec![(0, 1), (1, 0)],
+        );
+        assert_eq!(
+            grid.neighbours8((1, 1)).collect::<Vec<_>>(),
+            vec![(0, 1), (0, 2), (1, 2), (1, 0), (0, 0)],
+        );
+    }
+
+    #[test]
+    fn test_rows_and_columns() {
+        let grid = grid();
+        assert_eq!(grid.rows().collect::<Vec<_>>(), vec![&['a', 'b', 'c'], &['d', 'e', 'f']]);
+        assert_eq!(
+            grid.columns().map(values).collect::<Vec<_>>(),
+            vec!["ad", "be", "cf"],
+        );
+    }
+
+    #[test]
+    fn test_diagonals() {
+        let grid = grid();
+        assert_eq!(
+            grid.diagonals().map(values).collect::<Vec<_>>(),
+            vec!["d", "ae", "bf", "c"],
+        );
+        assert_eq!(
+            grid.anti_diagonals().map(values).collect::<Vec<_>>(),
+            vec!["a", "bd", "ce", "f"],
+        );
+    }
+
+    #[test]
+    fn test_line() {
+        let grid = grid();
+        assert_eq!(values(grid.line((1, 2), Direction::West)), "fed");
+        assert_eq!(values(grid.line((1, 0), Direction::NorthEast)), "db");
+        assert_eq!(values(grid.line((5, 5), Direction::East)), "");
+    }
+
+    #[test]
+    fn test_transformations() {
+        let grid = grid();
+        assert_eq!(grid.transpose().to_string(), "ad\nbe\ncf\n");
+        assert_eq!(grid.rotate_clockwise().to_string(), "da\neb\nfc\n");
+        assert_eq!(grid.rotate_counterclockwise().to_string(), "cf\nbe\nad\n");
+        assert_eq!(grid.flip_horizontal().to_string(), "cba\nfed\n");
+        assert_eq!(grid.flip_vertical().to_string(), "def\nabc\n");
+        assert_eq!(grid.rotate_clockwise().rotate_counterclockwise(), grid);
+    }
+
+    #[test]
+    fn test_subgrid() {
+        let grid = grid();
+        assert_eq!(grid.subgrid((0, 1), 2, 2).map(|g| g.to_string()), Some("bc\nef\n".to_string()));
+        assert_eq!(grid.subgrid((1, 1), 2, 2), None);
+    }
+
+    #[test]
+    fn test_view() {
+        let grid = grid();
+        let view = grid.view((0, 1), 2, 2).expect("View inside grid");
+        assert_eq!(view.origin(), (0, 1));
+        assert_eq!(view[(1, 0)], 'e');
+        assert_eq!(view.get((0, 2)), None);
+        assert_eq!(view.get((2, 0)), None);
+        assert_eq!(view.iter().map(|(_, &c)| c).collect::<String>(), "bcef");
+        assert!(grid.view((1, 2), 2, 1).is_none());
+        assert!(grid.view((usize::MAX, 0), 1, 1).is_none());
+        assert!(grid.view((0, 1), usize::MAX, 1).is_none());
+    }
+
+    #[test]
+    fn test_windows() {
+        let grid = grid();
+        let windows: Vec<String> = grid
+            .windows(2, 2)
+            .map(|view| view.to_grid().to_string())
+            .collect();
+        assert_eq!(windows, vec!["ab\nde\n", "bc\nef\n"]);
+        assert_eq!(grid.windows(3, 1).count(), 2);
+        assert_eq!(grid.windows(4, 1).count(), 0);
+        assert_eq!(grid.windows(1, 3).count(), 0);
+    }
+
+    #[test]
+    fn test_map() {
+        let grid = grid().map(|&c| c.is_ascii_lowercase() && c > 'c');
+        assert_eq!(grid.iter().filter(|(_, &v)| v).count(), 3);
+    }
+}
@@ -0,0 +1,83 @@
+use std::ops::Index;
+
+use crate::{Grid, Point};
+
+/// A borrowed rectangular window into a `Grid`, addressed relative to its origin.
+#[derive(Debug)]
+pub struct View<'a, T> {
+    grid: &'a Grid<T>,
+    origin: Point,
+    width: usize,
+    height: usize,
+}
+
+impl<T> Clone for View<'_, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for View<'_, T> {}
+
+impl<'a, T> View<'a, T> {
+    pub(crate) fn new(grid: &'a Grid<T>, origin: Point, width: usize, height: usize) -> Option<Self> {
+        let (row, col) = origin;
+        let fits = |start: usize, len: usize, limit: usize| start.checked_add(len).is_some_and(|end| end <= limit);
+        if !fits(row, height, grid.height()) || !fits(col, width, grid.width()) {
+            return None;
+        }
+        Some(Self { grid, origin, width, height })
+    }
+
+    pub fn origin(&self) -> Point {
+        self.origin
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn get(&self, (row, col): Point) -> Option<&'a T> {
+        if row < self.height && col < self.width {
+            self.grid.get((self.origin.0 + row, self.origin.1 + col))
+        } else {
+            None
+        }
+    }
+
+    /// Iterates over the cells of the window with positions relative to its origin.
+    pub fn iter(&self) -> impl Iterator<Item = (Point, &'a T)> + 'a {
+        let view = *self;
+        (0..view.height)
+            .flat_map(move |row| (0..view.width).map(move |col| (row, col)))
+            .map(move |point| (point, view.get(point).expect("Point inside view")))
+    }
+
+    pub fn to_grid(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        let cells = self.iter().map(|(_, cell)| cell.clone()).collect();
+        Grid::new(self.width, self.height, cells).expect("View dimensions match its cells")
+    }
+}
+
+impl<T> Index<Point> for View<'_, T> {
+    type Output = T;
+
+    fn index(&self, point: Point) -> &T {
+        self.get(point).expect("Point outside of view")
+    }
+}
+
+impl<T: PartialEq> PartialEq<Grid<T>> for View<'_, T> {
+    fn eq(&self, other: &Grid<T>) -> bool {
+        self.width == other.width()
+            && self.height == other.height()
+            && self.iter().all(|(point, cell)| *cell == other[point])
+    }
+}